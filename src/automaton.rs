@@ -0,0 +1,230 @@
+//! Life-like cellular automata: the cells are live or dead, and whether a cell is live in the
+//! next generation only depends on whether it's live now and how many of its neighbours are.
+//!
+//! Used for the seats of day 11, the cubes of day 17 and the hex tiles of day 24.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Which numbers of live neighbours make a dead cell come alive (birth) or keep a live cell
+/// alive (survival). All other cells are dead in the next generation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(
+        birth: impl IntoIterator<Item = usize>,
+        survival: impl IntoIterator<Item = usize>,
+    ) -> Rule {
+        Rule {
+            birth: birth.into_iter().collect(),
+            survival: survival.into_iter().collect(),
+        }
+    }
+
+    /// Conway's Game of Life, `B3/S23`.
+    pub fn life() -> Rule {
+        Rule::new(vec![3], vec![2, 3])
+    }
+
+    pub fn is_live(&self, live: bool, live_neighbours: usize) -> bool {
+        if live {
+            self.survival.contains(&live_neighbours)
+        } else {
+            self.birth.contains(&live_neighbours)
+        }
+    }
+}
+
+/// The result of running an automaton.
+#[derive(Clone, Debug)]
+pub struct Run<P> {
+    pub live: HashSet<P>,
+    /// The number of generations that were computed, not counting the one that showed that a
+    /// fixed point was reached.
+    pub generations: usize,
+    /// Whether the last generation is the same as the one before.
+    pub fixed_point: bool,
+}
+
+/// An automaton with cells of type `P` (e.g. coordinates) and a function returning the
+/// neighbours of a cell, as a `Vec` or any other iterable (e.g. a slice of precomputed ones).
+pub struct Automaton<P, N> {
+    neighbours: N,
+    rule: Rule,
+    /// All cells there are, if the space is bounded.
+    cells: Option<Vec<P>>,
+}
+
+impl<P, N, I> Automaton<P, N>
+where
+    P: Clone + Eq + Hash,
+    N: Fn(&P) -> I,
+    I: IntoIterator<Item = P>,
+{
+    /// An automaton in an unbounded space, where any neighbour of a live cell can come alive.
+    pub fn new(neighbours: N, rule: Rule) -> Automaton<P, N> {
+        Automaton {
+            neighbours,
+            rule,
+            cells: None,
+        }
+    }
+
+    /// Restricts the automaton to the given cells, e.g. the seats of a seating area. This is
+    /// needed for rules where cells without any live neighbours come alive.
+    pub fn within(mut self, cells: impl IntoIterator<Item = P>) -> Automaton<P, N> {
+        self.cells = Some(cells.into_iter().collect());
+        self
+    }
+
+    /// Computes the next generation.
+    pub fn step(&self, live: &HashSet<P>) -> HashSet<P> {
+        let mut counts: HashMap<P, usize> = HashMap::new();
+        for cell in live {
+            for neighbour in (self.neighbours)(cell) {
+                *counts.entry(neighbour).or_default() += 1;
+            }
+        }
+
+        let is_live = |cell: &P, count: usize| self.rule.is_live(live.contains(cell), count);
+        match &self.cells {
+            Some(cells) => cells
+                .iter()
+                .filter(|cell| is_live(cell, counts.get(cell).copied().unwrap_or(0)))
+                .cloned()
+                .collect(),
+            None => {
+                assert!(
+                    !self.rule.birth.contains(&0),
+                    "Birth without live neighbours needs a bounded space, see `within`"
+                );
+                let lonely = live.iter().filter(|cell| !counts.contains_key(cell));
+                counts
+                    .iter()
+                    .filter(|(cell, &count)| is_live(cell, count))
+                    .map(|(cell, _)| cell)
+                    .chain(lonely.filter(|cell| is_live(cell, 0)))
+                    .cloned()
+                    .collect()
+            }
+        }
+    }
+
+    /// Runs for the number of generations, or fewer if a fixed point is reached before.
+    pub fn run(&self, live: HashSet<P>, generations: usize) -> Run<P> {
+        self.run_until(live, Some(generations))
+    }
+
+    /// Runs until a generation is the same as the one before. Doesn't terminate for automata that
+    /// grow or oscillate forever.
+    pub fn run_to_fixed_point(&self, live: HashSet<P>) -> Run<P> {
+        self.run_until(live, None)
+    }
+
+    fn run_until(&self, mut live: HashSet<P>, limit: Option<usize>) -> Run<P> {
+        let mut generations = 0;
+        while limit.is_none_or(|limit| generations < limit) {
+            let next = self.step(&live);
+            if next == live {
+                return Run {
+                    live,
+                    generations,
+                    fixed_point: true,
+                };
+            }
+            live = next;
+            generations += 1;
+        }
+        Run {
+            live,
+            generations,
+            fixed_point: false,
+        }
+    }
+}
+
+/// The 8 neighbours of a cell in 2D, 26 in 3D, 80 in 4D etc. as offsets.
+pub fn moore_offsets(dimensions: usize) -> Vec<Vec<i64>> {
+    let mut offsets = vec![vec![]];
+    for _ in 0..dimensions {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|offset| {
+                (-1..=1).map(move |d| {
+                    let mut offset = offset.clone();
+                    offset.push(d);
+                    offset
+                })
+            })
+            .collect();
+    }
+    offsets.retain(|offset| offset.iter().any(|&d| d != 0));
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbours(&(x, y): &(i64, i64)) -> Vec<(i64, i64)> {
+        moore_offsets(2)
+            .iter()
+            .map(|d| (x + d[0], y + d[1]))
+            .collect()
+    }
+
+    fn cells(cells: &[(i64, i64)]) -> HashSet<(i64, i64)> {
+        cells.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_moore_offsets() {
+        assert_eq!(moore_offsets(2).len(), 8);
+        assert_eq!(moore_offsets(3).len(), 26);
+        assert_eq!(moore_offsets(4).len(), 80);
+    }
+
+    #[test]
+    fn test_blinker() {
+        let automaton = Automaton::new(neighbours, Rule::life());
+        let vertical = cells(&[(1, 0), (1, 1), (1, 2)]);
+        let horizontal = cells(&[(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(automaton.step(&vertical), horizontal);
+
+        let run = automaton.run(vertical.clone(), 4);
+        assert_eq!(run.live, vertical);
+        assert_eq!(run.generations, 4);
+        assert!(!run.fixed_point);
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let automaton = Automaton::new(neighbours, Rule::life());
+        // Three cells in an L shape become a block in one generation
+        let run = automaton.run_to_fixed_point(cells(&[(0, 0), (1, 0), (0, 1)]));
+        assert_eq!(run.live, cells(&[(0, 0), (1, 0), (0, 1), (1, 1)]));
+        assert_eq!(run.generations, 1);
+        assert!(run.fixed_point);
+
+        let run = automaton.run(cells(&[(0, 0)]), 10);
+        assert!(run.live.is_empty());
+        assert_eq!(run.generations, 1);
+    }
+
+    #[test]
+    fn test_bounded() {
+        // Cells come alive without neighbours and die when crowded, within a 1x3 strip
+        let automaton = Automaton::new(neighbours, Rule::new(vec![0], vec![0, 1])).within(vec![
+            (0, 0),
+            (1, 0),
+            (2, 0),
+        ]);
+        let run = automaton.run_to_fixed_point(HashSet::new());
+        assert_eq!(run.live, cells(&[(0, 0), (2, 0)]));
+        assert_eq!(run.generations, 2);
+    }
+}
@@ -0,0 +1,290 @@
+//! Benchmarking days by timing parsing, part 1 and part 2 separately over multiple runs.
+
+use crate::days;
+use crate::input::Source;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// The steps of running a day that are timed separately.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.iter().copied().find(|p| p.name() == name)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// The durations of a single run of a day.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timing {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timing {
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Parse => self.parse,
+            Phase::Part1 => self.part1,
+            Phase::Part2 => self.part2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// The stats of the durations, which must not be empty. For an even number of durations, the
+    /// median is the mean of the two middle ones.
+    pub fn of(durations: &[Duration]) -> Stats {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        Stats {
+            min: sorted[0],
+            median,
+            max: sorted[n - 1],
+        }
+    }
+}
+
+/// The stats of all phases of a day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayStats {
+    pub day: u32,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+/// Runs each of the selected days `runs` times and returns the stats of the timings.
+pub fn bench(selection: &[u32], source: &Source, runs: usize) -> Result<Vec<DayStats>, String> {
+    if selection.len() > 1 && !source.is_per_day() {
+        return Err("A single input can only be used when benchmarking a single day".to_string());
+    }
+    if runs == 0 {
+        return Err("The number of runs has to be at least 1".to_string());
+    }
+
+    selection
+        .iter()
+        .map(|&day| {
+            let input = source.load(day)?;
+            let d = days::get(day).expect("valid day");
+            let mut timings = Vec::with_capacity(runs);
+            for _ in 0..runs {
+                timings.push((d.run)(&input).map_err(|e| e.to_string())?.timing);
+            }
+            let phases = Phase::ALL
+                .iter()
+                .map(|&phase| {
+                    let durations: Vec<_> = timings.iter().map(|t| t.get(phase)).collect();
+                    (phase, Stats::of(&durations))
+                })
+                .collect();
+            Ok(DayStats { day, phases })
+        })
+        .collect()
+}
+
+/// Median durations of a previous benchmark, to compare against.
+pub type Baseline = HashMap<(u32, Phase), Duration>;
+
+/// The medians of the stats as a baseline.
+pub fn baseline(stats: &[DayStats]) -> Baseline {
+    let mut baseline = Baseline::new();
+    for day_stats in stats {
+        for (phase, s) in &day_stats.phases {
+            baseline.insert((day_stats.day, *phase), s.median);
+        }
+    }
+    baseline
+}
+
+/// Formats the baseline as lines like `7 part1 123456` with the median in nanoseconds.
+pub fn format_baseline(baseline: &Baseline) -> String {
+    let mut entries: Vec<_> = baseline.iter().collect();
+    entries.sort_by_key(|((day, phase), _)| (*day, Phase::ALL.iter().position(|p| p == phase)));
+    entries
+        .iter()
+        .map(|((day, phase), median)| format!("{} {} {}\n", day, phase, median.as_nanos()))
+        .collect()
+}
+
+pub fn parse_baseline(s: &str) -> Option<Baseline> {
+    let mut baseline = HashMap::new();
+    for line in s.lines() {
+        let mut parts = line.split(' ');
+        let day = parts.next()?.parse().ok()?;
+        let phase = Phase::from_name(parts.next()?)?;
+        let nanos = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        baseline.insert((day, phase), Duration::from_nanos(nanos));
+    }
+    Some(baseline)
+}
+
+/// Saves the medians to a baseline file. Days that are in the file already but weren't
+/// benchmarked this time are kept, so that a baseline can be built up a few days at a time.
+pub fn save_baseline(file: &Path, stats: &[DayStats]) -> Result<(), String> {
+    let mut baseline = if file.exists() {
+        load_baseline(file)?
+    } else {
+        Baseline::new()
+    };
+    baseline.extend(self::baseline(stats));
+    fs::write(file, format_baseline(&baseline))
+        .map_err(|e| format!("Could not write baseline to {}: {}", file.display(), e))
+}
+
+pub fn load_baseline(file: &Path) -> Result<Baseline, String> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| format!("Could not read baseline from {}: {}", file.display(), e))?;
+    parse_baseline(&content).ok_or_else(|| format!("Invalid baseline file: {}", file.display()))
+}
+
+/// The change of `median` relative to `base` in percent, positive if it got slower.
+pub fn change(median: Duration, base: Duration) -> f64 {
+    (median.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0
+}
+
+/// Prints a table with one row per day and phase, with the change of the median compared to the
+/// baseline if there is one.
+pub fn print_stats(stats: &[DayStats], baseline: Option<&Baseline>) {
+    print!("Day  Phase  {:>12}  {:>12}  {:>12}", "Min", "Median", "Max");
+    if baseline.is_some() {
+        print!("  {:>12}  Change", "Baseline");
+    }
+    println!();
+
+    for day_stats in stats {
+        for (phase, s) in &day_stats.phases {
+            print!(
+                "{:>3}  {:5}  {:>12}  {:>12}  {:>12}",
+                day_stats.day,
+                phase,
+                format_duration(s.min),
+                format_duration(s.median),
+                format_duration(s.max)
+            );
+            if let Some(baseline) = baseline {
+                match baseline.get(&(day_stats.day, *phase)) {
+                    Some(&base) if base > Duration::from_nanos(0) => print!(
+                        "  {:>12}  {:+.1}%",
+                        format_duration(base),
+                        change(s.median, base)
+                    ),
+                    Some(&base) => print!("  {:>12}  -", format_duration(base)),
+                    None => print!("  {:>12}  -", "-"),
+                }
+            }
+            println!();
+        }
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3?}", d)
+}
+
+/// Benchmarks the selected days, prints the stats and compares them to and/or saves a baseline.
+pub fn print_bench(
+    selection: &[u32],
+    source: &Source,
+    runs: usize,
+    compare: Option<&Path>,
+    save: Option<&Path>,
+) -> Result<(), String> {
+    // Load before running, so that a missing file doesn't waste a long benchmark
+    let baseline = compare.map(load_baseline).transpose()?;
+    let stats = bench(selection, source, runs)?;
+    print_stats(&stats, baseline.as_ref());
+    if let Some(file) = save {
+        save_baseline(file, &stats)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            Stats::of(&[ms(5), ms(1), ms(3)]),
+            Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            }
+        );
+        assert_eq!(Stats::of(&[ms(4), ms(1), ms(2), ms(9)]).median, ms(3));
+        assert_eq!(Stats::of(&[ms(7)]).median, ms(7));
+    }
+
+    #[test]
+    fn test_baseline() {
+        let stats = vec![DayStats {
+            day: 7,
+            phases: vec![
+                (Phase::Parse, Stats::of(&[ms(1)])),
+                (Phase::Part1, Stats::of(&[ms(2), ms(4)])),
+            ],
+        }];
+        let s = format_baseline(&baseline(&stats));
+        assert_eq!(s, "7 parse 1000000\n7 part1 3000000\n");
+
+        let baseline = parse_baseline(&s).unwrap();
+        assert_eq!(baseline[&(7, Phase::Part1)], ms(3));
+        assert_eq!(baseline.len(), 2);
+
+        assert_eq!(parse_baseline("7 part3 100"), None);
+        assert_eq!(parse_baseline("7 part1"), None);
+    }
+
+    #[test]
+    fn test_change() {
+        assert!((change(ms(150), ms(100)) - 50.0).abs() < 1e-9);
+        assert!((change(ms(50), ms(100)) + 50.0).abs() < 1e-9);
+    }
+}
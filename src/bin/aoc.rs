@@ -0,0 +1,187 @@
+//! Runner for all days:
+//!
+//! ```text
+//! aoc run 7
+//! aoc run 1..=25
+//! aoc run all
+//! aoc run 7 --input my-input.txt
+//! aoc run all --format json
+//! aoc run all --inputs inputs
+//! aoc bench 15 --runs 5 --save-baseline bench.txt
+//! aoc bench all --baseline bench.txt
+//! aoc verify
+//! aoc verify 7 --record
+//! aoc grammar --format dot
+//! aoc image --output image
+//! ```
+
+use advent_of_code_2020::days::{day19, day20};
+use advent_of_code_2020::grammar::{self, Analysis};
+use advent_of_code_2020::input::Source;
+use advent_of_code_2020::{bench, cli, multi, runner, verify};
+use std::env;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "Usage: aoc run <days>... [options]
+       aoc bench <days>... [options] [bench options]
+       aoc verify [<days>...] [--input-dir <dir>] [--record]
+       aoc grammar [--input <file> | --input-dir <dir>] [--format <format>]
+       aoc image --output <dir> [--input <file> | --input-dir <dir>] [--limit <n>]
+
+Days can be a single day like `7`, a range like `1..=25` or `1..26`, or `all`.
+
+Options:
+    --input <file>      Read the input from a file, or from stdin with `-` (single day only)
+    --input-dir <dir>   Read the inputs from <dir>/2020/dayNN.txt, defaults to $AOC_INPUT_DIR or `input`
+    --format <format>   Print the answers as a `table` (default) or as `json` with timings (run only)
+    --inputs <dir>      Run with the inputs of every user in <dir>/<user>/2020/dayNN.txt and report
+                        their answers and failures side by side (run only)
+
+Bench options:
+    --runs <n>              How many times to run each day, defaults to 10
+    --baseline <file>       Compare the medians to a baseline saved earlier
+    --save-baseline <file>  Save the medians as a baseline, keeping other days in the file
+
+Verify compares the answers to the accepted ones in <dir>/2020/answers.txt, for all days by default.
+Verify options:
+    --record                Record the answers of parts that have no accepted answer yet
+
+Grammar analyses the rules of day 19: undefined, unreachable and recursive rules, and the lengths
+of the strings that rules match, up to the length of the longest message.
+Grammar options:
+    --format <format>       Print the analysis as a `report` (default), or the rules as `ebnf` or
+                            as a Graphviz `dot` graph
+
+Image assembles the tiles of day 20 and writes the image without the tile borders to <dir>: as text
+(image.txt), as a PBM bitmap (image.pbm) and as a PGM graymap with the sea monsters in gray
+(image.pgm). The arrangement map (arrangement.txt) lists the position, orientation and sides of
+each tile. If the tiles fit together in more than one way, the first arrangement is written.
+Arrangements that are rotations or flips of the whole image of another one are not counted.
+Image options:
+    --output <dir>          The directory to write the files to, created if it doesn't exist
+    --limit <n>             How many arrangements to look for, defaults to 10";
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_ARRANGEMENTS: usize = 10;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    cli::exit_on_error(run(args));
+}
+
+fn run(mut args: Vec<String>) -> Result<(), String> {
+    if args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let command = args.remove(0);
+    match command.as_str() {
+        "run" => {
+            if let Some(dir) = cli::take_option(&mut args, "--inputs")? {
+                cli::check_no_options(&args)
+                    .map_err(|e| format!("{} (can't be combined with --inputs)", e))?;
+                let selection = runner::parse_selection(&args)?;
+                return multi::print_multi(&selection, dir.as_ref());
+            }
+            let source = cli::take_input_source(&mut args)?;
+            let format = cli::take_format(&mut args)?;
+            cli::check_no_options(&args)?;
+            let selection = runner::parse_selection(&args)?;
+            runner::print_days(&selection, &source, format)
+        }
+        "bench" => {
+            let source = cli::take_input_source(&mut args)?;
+            let runs = match cli::take_option(&mut args, "--runs")? {
+                Some(runs) => runs
+                    .parse()
+                    .map_err(|_| format!("Not a valid number of runs: {:?}", runs))?,
+                None => DEFAULT_RUNS,
+            };
+            let compare = cli::take_option(&mut args, "--baseline")?.map(PathBuf::from);
+            let save = cli::take_option(&mut args, "--save-baseline")?.map(PathBuf::from);
+            cli::check_no_options(&args)?;
+            let selection = runner::parse_selection(&args)?;
+            bench::print_bench(
+                &selection,
+                &source,
+                runs,
+                compare.as_deref(),
+                save.as_deref(),
+            )
+        }
+        "verify" => {
+            let dir = match cli::take_input_source(&mut args)? {
+                Source::Dir(dir) => dir,
+                _ => return Err("Verify needs an input directory, use --input-dir".to_string()),
+            };
+            let record = cli::take_flag(&mut args, "--record");
+            cli::check_no_options(&args)?;
+            let selection = if args.is_empty() {
+                runner::parse_selection(&["all"])?
+            } else {
+                runner::parse_selection(&args)?
+            };
+            verify::print_verify(&selection, &dir, record)
+        }
+        "grammar" => {
+            let source = cli::take_input_source(&mut args)?;
+            let format = cli::take_option(&mut args, "--format")?;
+            cli::check_no_options(&args)?;
+            if !args.is_empty() {
+                return Err(USAGE.to_string());
+            }
+            let input = source.load(19)?;
+            // Unchecked, so that undefined rules can be reported by the analysis
+            let puzzle = day19::parse_unchecked(&input).map_err(|e| e.to_string())?;
+            let rules = puzzle.rules();
+            let max_length = puzzle.messages().iter().map(|m| m.len()).max().unwrap_or(0);
+            let analysis = Analysis::new(rules, 0, max_length);
+            match format.as_deref() {
+                None | Some("report") => println!("{}", analysis),
+                Some("ebnf") => print!("{}", grammar::ebnf(rules)),
+                Some("dot") => print!("{}", grammar::dot(rules, &analysis)),
+                Some(format) => {
+                    return Err(format!(
+                        "Unknown grammar format {:?}, expected `report`, `ebnf` or `dot`",
+                        format
+                    ))
+                }
+            }
+            Ok(())
+        }
+        "image" => {
+            let source = cli::take_input_source(&mut args)?;
+            let output = cli::take_option(&mut args, "--output")?
+                .ok_or("Image needs an output directory, use --output")?;
+            let limit = match cli::take_option(&mut args, "--limit")? {
+                Some(limit) => limit
+                    .parse()
+                    .ok()
+                    .filter(|&limit| limit > 0)
+                    .ok_or_else(|| format!("Not a valid number of arrangements: {:?}", limit))?,
+                None => DEFAULT_ARRANGEMENTS,
+            };
+            cli::check_no_options(&args)?;
+            if !args.is_empty() {
+                return Err(USAGE.to_string());
+            }
+            let input = source.load(20)?;
+            let puzzle = day20::parse(&input).map_err(|e| e.to_string())?;
+            let assembly = day20::assemble_all(&puzzle, limit);
+            let arrangement = assembly
+                .arrangements
+                .first()
+                .ok_or("The tiles can't be arranged so that all neighbouring sides match")?;
+            let count = assembly.arrangements.len();
+            match (count, assembly.complete) {
+                (1, true) => println!("The arrangement is unique"),
+                (_, true) => println!("Found {} arrangements", count),
+                (_, false) => println!("Found at least {} arrangements", count),
+            }
+            let dir = Path::new(&output);
+            day20::export(&puzzle, arrangement, dir)?;
+            println!("Wrote the image and the arrangement to {}", dir.display());
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
@@ -0,0 +1,394 @@
+//! A rectangular 2D grid of bits, packed into `u64` words row by row, for black and white images
+//! that are transformed and searched a lot (day 20).
+//!
+//! Positions are `(x, y)` like in [`crate::grid`]. Within a row, bit `i` of word `k` is the pixel
+//! at `x = 64 * k + i`, so a run of pixels read as an integer has the leftmost one in the lowest
+//! bit. Bits past the width are always 0, so that grids can be compared with `==`.
+
+use crate::grid::{Grid, Pos};
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row.
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with all bits cleared.
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        let stride = width.div_ceil(64);
+        BitGrid {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> bool) -> BitGrid {
+        let mut grid = BitGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if f((x, y)) {
+                    grid.set((x, y), true);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> bool {
+        assert!(
+            self.contains((x, y)),
+            "Position {:?} outside of grid",
+            (x, y)
+        );
+        self.row(y)[x / 64] >> (x % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, (x, y): Pos, value: bool) {
+        assert!(
+            self.contains((x, y)),
+            "Position {:?} outside of grid",
+            (x, y)
+        );
+        let word = &mut self.row_mut(y)[x / 64];
+        if value {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    /// The number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The positions of the set bits in row order.
+    pub fn ones(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |y| {
+            self.row(y)
+                .iter()
+                .enumerate()
+                .flat_map(move |(k, &word)| BitIter(word).map(move |i| (64 * k + i, y)))
+        })
+    }
+
+    /// `length` bits (at most 64) of row `y` starting at column `x`, with the pixel at `x` in
+    /// the lowest bit.
+    pub fn row_bits(&self, x: usize, y: usize, length: usize) -> u64 {
+        assert!(length <= 64 && x + length <= self.width && y < self.height);
+        if length == 0 {
+            return 0;
+        }
+        shifted_word(self.row(y), x, 0) & mask(length)
+    }
+
+    /// `length` bits (at most 64) of column `x` starting at row `y`, with the pixel at `y` in the
+    /// lowest bit.
+    pub fn column_bits(&self, x: usize, y: usize, length: usize) -> u64 {
+        assert!(length <= 64 && x < self.width && y + length <= self.height);
+        (0..length).fold(0, |bits, i| bits | (self.get((x, y + i)) as u64) << i)
+    }
+
+    /// Overwrites `length` bits (at most 64) of row `y` starting at column `x` with `bits`, the
+    /// lowest bit going to `x`.
+    pub fn set_row_bits(&mut self, x: usize, y: usize, bits: u64, length: usize) {
+        assert!(length <= 64 && x + length <= self.width && y < self.height);
+        if length == 0 {
+            return;
+        }
+        let bits = bits & mask(length);
+        let (k, offset) = (x / 64, x % 64);
+        let row = self.row_mut(y);
+        row[k] = row[k] & !(mask(length) << offset) | bits << offset;
+        if offset + length > 64 {
+            let rest = offset + length - 64;
+            row[k + 1] = row[k + 1] & !mask(rest) | bits >> (64 - offset);
+        }
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> BitGrid {
+        let mut flipped = BitGrid::new(self.width, self.height);
+        // Reversing all the words of a row puts column x at 64 * stride - 1 - x, which is then
+        // shifted to width - 1 - x.
+        let padding = 64 * self.stride - self.width;
+        for y in 0..self.height {
+            let reversed: Vec<u64> = self.row(y).iter().rev().map(|w| w.reverse_bits()).collect();
+            for (k, word) in flipped.row_mut(y).iter_mut().enumerate() {
+                *word = shifted_word(&reversed, padding, k);
+            }
+        }
+        flipped
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> BitGrid {
+        let mut flipped = BitGrid::new(self.width, self.height);
+        for y in 0..self.height {
+            let source = self.height - 1 - y;
+            flipped.row_mut(y).copy_from_slice(self.row(source));
+        }
+        flipped
+    }
+
+    /// Mirrors the grid along the diagonal from the top left, swapping `x` and `y`.
+    ///
+    /// Works on blocks of 64x64 bits: word `k` of 64 rows starting at `64 * b` becomes word `b`
+    /// of the 64 rows starting at `64 * k`.
+    pub fn transpose(&self) -> BitGrid {
+        let mut transposed = BitGrid::new(self.height, self.width);
+        for block_y in 0..transposed.stride {
+            for k in 0..self.stride {
+                let mut block = [0; 64];
+                let rows = 64 * block_y..self.height.min(64 * block_y + 64);
+                for (word, y) in block.iter_mut().zip(rows) {
+                    *word = self.row(y)[k];
+                }
+                transpose_block(&mut block);
+                for (i, &word) in block.iter().enumerate().take(self.width - 64 * k) {
+                    transposed.row_mut(64 * k + i)[block_y] = word;
+                }
+            }
+        }
+        transposed
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    pub fn rotate_left(&self) -> BitGrid {
+        self.transpose().flip_vertical()
+    }
+
+    /// The top left positions where all the set bits of `pattern` are also set in this grid.
+    ///
+    /// Works a word (64 positions) at a time: the candidates of a row start out as all positions
+    /// where the pattern fits, and are and-ed with the image rows shifted by the column of each
+    /// set bit of the pattern.
+    pub fn find(&self, pattern: &BitGrid) -> Vec<Pos> {
+        let mut found = Vec::new();
+        if pattern.width > self.width || pattern.height > self.height {
+            return found;
+        }
+        let xs = self.width - pattern.width + 1;
+        let pattern_ones: Vec<Pos> = pattern.ones().collect();
+        let mut candidates = vec![0; xs.div_ceil(64)];
+        for y in 0..=(self.height - pattern.height) {
+            for (k, word) in candidates.iter_mut().enumerate() {
+                *word = mask((xs - 64 * k).min(64));
+            }
+            for &(px, py) in &pattern_ones {
+                let row = self.row(y + py);
+                let mut any = 0;
+                for (k, word) in candidates.iter_mut().enumerate() {
+                    *word &= shifted_word(row, px, k);
+                    any |= *word;
+                }
+                if any == 0 {
+                    break;
+                }
+            }
+            for (k, &word) in candidates.iter().enumerate() {
+                found.extend(BitIter(word).map(|i| (64 * k + i, y)));
+            }
+        }
+        found
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_fn(self.width, self.height, |pos| self.get(pos))
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.words[y * self.stride..(y + 1) * self.stride]
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> BitGrid {
+        BitGrid::from_fn(grid.width(), grid.height(), |pos| grid[pos])
+    }
+}
+
+/// Renders the grid with `#` for set bits and `.` for others, one line per row.
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get((x, y)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The lowest `length` bits set, for `length` from 0 to 64.
+fn mask(length: usize) -> u64 {
+    if length == 64 {
+        u64::MAX
+    } else {
+        (1 << length) - 1
+    }
+}
+
+/// Word `k` of the row shifted right by `shift` bits, i.e. bit `i` is the bit at
+/// `64 * k + i + shift` of the row, and 0 past its end.
+fn shifted_word(row: &[u64], shift: usize, k: usize) -> u64 {
+    let (start, offset) = (k + shift / 64, shift % 64);
+    let low = row.get(start).copied().unwrap_or(0);
+    if offset == 0 {
+        low
+    } else {
+        let high = row.get(start + 1).copied().unwrap_or(0);
+        low >> offset | high << (64 - offset)
+    }
+}
+
+/// Transposes 64x64 bits in place, so that bit `j` of word `i` ends up as bit `i` of word `j`.
+/// Swaps the top right and bottom left quarters, then the same within each quarter and so on,
+/// each time for all the blocks of the same size at once.
+fn transpose_block(block: &mut [u64; 64]) {
+    let mut size = 32;
+    // The lower `size` bits of each group of `2 * size` bits
+    let mut low: u64 = 0x0000_0000_ffff_ffff;
+    while size != 0 {
+        let mut i = 0;
+        while i < 64 {
+            let swapped = ((block[i] >> size) ^ block[i + size]) & low;
+            block[i] ^= swapped << size;
+            block[i + size] ^= swapped;
+            // The next row with the `size` bit clear
+            i = (i + size + 1) & !size;
+        }
+        size /= 2;
+        low ^= low << size;
+    }
+}
+
+/// The indexes of the set bits of a word, lowest first.
+struct BitIter(u64);
+
+impl Iterator for BitIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let i = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Not symmetric, and wider than a word.
+    fn sample(width: usize, height: usize) -> BitGrid {
+        BitGrid::from_fn(width, height, |(x, y)| (x * 7 + y * 13 + x * y) % 5 < 2)
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = BitGrid::new(70, 2);
+        grid.set((65, 1), true);
+        grid.set((3, 0), true);
+        assert!(grid.get((65, 1)));
+        assert!(!grid.get((64, 1)));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.ones().collect::<Vec<_>>(), vec![(3, 0), (65, 1)]);
+        grid.set((3, 0), false);
+        assert_eq!(grid.count_ones(), 1);
+
+        let grid = BitGrid::from(&Grid::new(3, 2, vec![true, false, true, false, true, true]));
+        assert_eq!(grid.to_string(), "#.#\n.##\n");
+        assert_eq!(grid.to_grid().count(|&b| b), 4);
+    }
+
+    #[test]
+    fn test_bits() {
+        let grid = sample(100, 70);
+        for &(x, y, length) in &[(0, 0, 10), (60, 3, 10), (36, 5, 64), (99, 69, 1), (5, 0, 0)] {
+            let expected = (0..length).fold(0, |b, i| b | (grid.get((x + i, y)) as u64) << i);
+            assert_eq!(grid.row_bits(x, y, length), expected);
+            if x % 70 + length <= 70 {
+                let expected =
+                    (0..length).fold(0, |b, i| b | (grid.get((y, x % 70 + i)) as u64) << i);
+                assert_eq!(grid.column_bits(y, x % 70, length), expected);
+            }
+
+            let mut copy = grid.clone();
+            copy.set_row_bits(x, y, !grid.row_bits(x, y, length), length);
+            let changed = (0..100).filter(|&i| copy.get((i, y)) != grid.get((i, y)));
+            assert_eq!(
+                changed.collect::<Vec<_>>(),
+                (x..x + length).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        for &(width, height) in &[(3, 2), (64, 5), (100, 70), (130, 129)] {
+            let grid = sample(width, height);
+            let flipped = grid.flip_horizontal();
+            let (flipped_vertical, transposed) = (grid.flip_vertical(), grid.transpose());
+            let rotated = grid.rotate_left();
+            assert_eq!((rotated.width(), rotated.height()), (height, width));
+            for y in 0..height {
+                for x in 0..width {
+                    let value = grid.get((x, y));
+                    assert_eq!(flipped.get((width - 1 - x, y)), value);
+                    assert_eq!(flipped_vertical.get((x, height - 1 - y)), value);
+                    assert_eq!(transposed.get((y, x)), value);
+                    assert_eq!(rotated.get((y, width - 1 - x)), value);
+                }
+            }
+            // Padding stays clear, so equality works
+            assert_eq!(flipped.flip_horizontal(), grid);
+            assert_eq!(rotated.rotate_left().rotate_left().rotate_left(), grid);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let image = sample(150, 40);
+        // Part of the image, so that it's found at least once
+        let pattern = BitGrid::from_fn(4, 3, |(x, y)| {
+            (x + y).is_multiple_of(2) && image.get((70 + x, 20 + y))
+        });
+        let expected: Vec<Pos> = (0..38)
+            .flat_map(|y| (0..147).map(move |x| (x, y)))
+            .filter(|&(x, y)| pattern.ones().all(|(px, py)| image.get((x + px, y + py))))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(image.find(&pattern), expected);
+
+        assert!(image.find(&BitGrid::new(151, 1)).is_empty());
+        assert_eq!(BitGrid::new(2, 2).find(&BitGrid::new(2, 2)), vec![(0, 0)]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Command line handling shared by the `aoc` and `dayNN` binaries.
+
+use crate::input::Source;
+use crate::runner::{self, Format};
+use std::{env, process};
+
+/// Removes `--name value` or `--name=value` from the arguments and returns the value.
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", name);
+    let mut value = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == name {
+            if i + 1 == args.len() {
+                return Err(format!("Missing value for {}", name));
+            }
+            value = Some(args.remove(i + 1));
+            args.remove(i);
+        } else if let Some(v) = args[i].strip_prefix(&prefix) {
+            value = Some(v.to_string());
+            args.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(value)
+}
+
+/// Removes `--name` from the arguments and returns whether it was there.
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != len
+}
+
+/// Returns an error for options that no command took, e.g. typos.
+pub fn check_no_options(args: &[String]) -> Result<(), String> {
+    match args.iter().find(|arg| arg.starts_with("--")) {
+        Some(arg) => Err(format!("Unknown option: {}", arg)),
+        None => Ok(()),
+    }
+}
+
+/// Takes `--input <file>` (`-` for stdin) or `--input-dir <dir>` from the arguments.
+pub fn take_input_source(args: &mut Vec<String>) -> Result<Source, String> {
+    let input = take_option(args, "--input")?;
+    let input_dir = take_option(args, "--input-dir")?;
+    match (input, input_dir) {
+        (Some(_), Some(_)) => Err("Use either --input or --input-dir, not both".to_string()),
+        (Some(file), None) if file == "-" => Ok(Source::Stdin),
+        (Some(file), None) => Ok(Source::File(file.into())),
+        (None, Some(dir)) => Ok(Source::Dir(dir.into())),
+        (None, None) => Ok(Source::default_dir()),
+    }
+}
+
+/// Takes `--format table` or `--format json` from the arguments, defaults to a table.
+pub fn take_format(args: &mut Vec<String>) -> Result<Format, String> {
+    match take_option(args, "--format")? {
+        Some(format) => Format::parse(&format),
+        None => Ok(Format::Table),
+    }
+}
+
+/// Prints the error and exits with a failure code if there is one.
+pub fn exit_on_error(result: Result<(), String>) {
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+/// The `main` of the `dayNN` binaries, which accept the same input and format options as
+/// `aoc run`.
+pub fn day_main(day: u32) {
+    exit_on_error(run_day(day));
+}
+
+fn run_day(day: u32) -> Result<(), String> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let source = take_input_source(&mut args)?;
+    let format = take_format(&mut args)?;
+    check_no_options(&args)?;
+    if let Some(arg) = args.first() {
+        return Err(format!("Unexpected argument: {}", arg));
+    }
+    runner::print_days(&[day], &source, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_option() {
+        let mut a = args(&["7", "--input", "foo.txt", "8"]);
+        assert_eq!(
+            take_option(&mut a, "--input"),
+            Ok(Some("foo.txt".to_string()))
+        );
+        assert_eq!(a, args(&["7", "8"]));
+
+        let mut a = args(&["--input=-", "7"]);
+        assert_eq!(take_option(&mut a, "--input"), Ok(Some("-".to_string())));
+        assert_eq!(a, args(&["7"]));
+
+        let mut a = args(&["7"]);
+        assert_eq!(take_option(&mut a, "--input"), Ok(None));
+
+        let mut a = args(&["7", "--input"]);
+        assert!(take_option(&mut a, "--input").is_err());
+    }
+
+    #[test]
+    fn test_take_flag() {
+        let mut a = args(&["7", "--record"]);
+        assert!(take_flag(&mut a, "--record"));
+        assert_eq!(a, args(&["7"]));
+        assert!(!take_flag(&mut a, "--record"));
+    }
+
+    #[test]
+    fn test_take_input_source() {
+        let mut a = args(&["--input", "-"]);
+        assert_eq!(take_input_source(&mut a), Ok(Source::Stdin));
+
+        let mut a = args(&["--input-dir", "other"]);
+        assert_eq!(take_input_source(&mut a), Ok(Source::Dir("other".into())));
+
+        let mut a = args(&["--input", "a.txt", "--input-dir", "other"]);
+        assert!(take_input_source(&mut a).is_err());
+    }
+
+    #[test]
+    fn test_check_no_options() {
+        assert!(check_no_options(&args(&["1..=3", "-"])).is_ok());
+        assert!(check_no_options(&args(&["1", "--inptu", "x"])).is_err());
+    }
+}
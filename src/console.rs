@@ -0,0 +1,336 @@
+//! The handheld game console from day 8: a machine that runs a program of instructions, each
+//! an operation with a signed argument like `acc +3` or `jmp -4`.
+//!
+//! To add an operation, add it to [`Op`] and [`Op::ALL`], give it a name and implement it in
+//! [`Machine::step`] (and in [`repairs`] if it affects the program counter or accumulator).
+
+use crate::parse::{ParseError, Text};
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    /// Does nothing.
+    Nop,
+    /// Adds the argument to the accumulator.
+    Acc,
+    /// Jumps relative to the current instruction.
+    Jmp,
+}
+
+impl Op {
+    pub const ALL: [Op; 3] = [Op::Nop, Op::Acc, Op::Jmp];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Nop => "nop",
+            Op::Acc => "acc",
+            Op::Jmp => "jmp",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Op> {
+        Op::ALL.iter().copied().find(|op| op.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub op: Op,
+    pub arg: i64,
+}
+
+impl Instruction {
+    pub fn new(op: Op, arg: i64) -> Instruction {
+        Instruction { op, arg }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:+}", self.op.name(), self.arg)
+    }
+}
+
+/// Parses a program with one instruction per line, like `acc +3`.
+pub fn parse_program(text: &Text, input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let expected_op = Op::ALL
+        .iter()
+        .map(|op| format!("`{}`", op.name()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    input
+        .lines()
+        .map(|line| {
+            let (op, arg) = text.split_once(line, " ")?;
+            let op = Op::from_name(op)
+                .ok_or_else(|| text.error(op, &format!("an operation ({})", expected_op)))?;
+            if !arg.starts_with(&['+', '-'][..]) {
+                return Err(text.error(arg, "a signed argument like `+3` or `-4`"));
+            }
+            let arg = text.parse(arg, "a signed argument like `+3` or `-4`")?;
+            Ok(Instruction { op, arg })
+        })
+        .collect()
+}
+
+/// The registers of the machine.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Registers {
+    pub acc: i64,
+}
+
+/// Whether the machine can continue, or why not.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Running,
+    /// The program counter is right after the last instruction.
+    Terminated,
+    /// The instruction at the program counter was executed before, so the machine would run
+    /// forever.
+    InfiniteLoop {
+        pc: usize,
+    },
+    /// A jump went before the first or further than right after the last instruction.
+    OutOfBounds {
+        pc: i64,
+    },
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Running => write!(f, "running"),
+            Status::Terminated => write!(f, "terminated"),
+            Status::InfiniteLoop { pc } => write!(f, "infinite loop at pc {}", pc),
+            Status::OutOfBounds { pc } => write!(f, "jump out of bounds to pc {}", pc),
+        }
+    }
+}
+
+pub struct Machine<'a> {
+    program: &'a [Instruction],
+    pub pc: i64,
+    pub registers: Registers,
+    /// Which instructions have been executed, for detecting infinite loops.
+    executed: Vec<bool>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(program: &'a [Instruction]) -> Machine<'a> {
+        Machine {
+            program,
+            pc: 0,
+            registers: Registers::default(),
+            executed: vec![false; program.len()],
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        let len = self.program.len() as i64;
+        if self.pc == len {
+            Status::Terminated
+        } else if self.pc < 0 || self.pc > len {
+            Status::OutOfBounds { pc: self.pc }
+        } else if self.executed[self.pc as usize] {
+            Status::InfiniteLoop {
+                pc: self.pc as usize,
+            }
+        } else {
+            Status::Running
+        }
+    }
+
+    /// Executes the instruction at the program counter if the machine is still running, and
+    /// returns the status after that.
+    pub fn step(&mut self) -> Status {
+        let status = self.status();
+        if status != Status::Running {
+            return status;
+        }
+
+        let pc = self.pc as usize;
+        self.executed[pc] = true;
+        let Instruction { op, arg } = self.program[pc];
+        match op {
+            Op::Nop => self.pc += 1,
+            Op::Acc => {
+                self.registers.acc += arg;
+                self.pc += 1;
+            }
+            Op::Jmp => self.pc += arg,
+        }
+        self.status()
+    }
+
+    /// Executes instructions until the machine stops running.
+    pub fn run(&mut self) -> Status {
+        loop {
+            let status = self.step();
+            if status != Status::Running {
+                return status;
+            }
+        }
+    }
+}
+
+/// A change of a single instruction that makes the program terminate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Repair {
+    pub pc: usize,
+    /// The instruction to use at `pc` instead.
+    pub replacement: Instruction,
+    /// The accumulator when the repaired program terminates.
+    pub acc: i64,
+}
+
+/// All the ways to make a program that doesn't terminate terminate by changing one `nop` to a
+/// `jmp` or the other way around, in the order in which they are on the execution path.
+///
+/// Runs in linear time: First, all instructions from which the program terminates are found by
+/// walking the jumps backwards from the end. Then the original execution path is followed, and
+/// each instruction on it where the changed instruction would continue at one of those is a
+/// repair. (An instruction from which the program terminates can't be on the original path,
+/// otherwise it would terminate without a change.)
+pub fn repairs(program: &[Instruction]) -> Vec<Repair> {
+    let len = program.len();
+    // The instruction executed after the one at `pc`, or `len` for termination
+    let next = |pc: usize, instruction: Instruction| -> Option<usize> {
+        let next = match instruction.op {
+            Op::Jmp => pc as i64 + instruction.arg,
+            Op::Nop | Op::Acc => pc as i64 + 1,
+        };
+        if (0..=len as i64).contains(&next) {
+            Some(next as usize)
+        } else {
+            None
+        }
+    };
+    let acc_change = |instruction: Instruction| match instruction.op {
+        Op::Acc => instruction.arg,
+        Op::Nop | Op::Jmp => 0,
+    };
+
+    let mut previous = vec![Vec::new(); len + 1];
+    for (pc, &instruction) in program.iter().enumerate() {
+        if let Some(next) = next(pc, instruction) {
+            previous[next].push(pc);
+        }
+    }
+
+    // For instructions from which the program terminates, how much the accumulator changes
+    // until then
+    let mut acc_to_end = vec![None; len + 1];
+    acc_to_end[len] = Some(0);
+    let mut queue = VecDeque::new();
+    queue.push_back(len);
+    while let Some(pc) = queue.pop_front() {
+        let acc = acc_to_end[pc].unwrap();
+        for &p in &previous[pc] {
+            acc_to_end[p] = Some(acc_change(program[p]) + acc);
+            queue.push_back(p);
+        }
+    }
+    if acc_to_end[0].is_some() {
+        // Terminates already
+        return Vec::new();
+    }
+
+    let mut repairs = Vec::new();
+    let mut executed = vec![false; len];
+    let mut pc = 0;
+    let mut acc = 0;
+    while pc < len && !executed[pc] {
+        executed[pc] = true;
+        let instruction = program[pc];
+        let replacement = match instruction.op {
+            Op::Nop => Some(Instruction::new(Op::Jmp, instruction.arg)),
+            Op::Jmp => Some(Instruction::new(Op::Nop, instruction.arg)),
+            Op::Acc => None,
+        };
+        if let Some(replacement) = replacement {
+            if let Some(rest) = next(pc, replacement).and_then(|n| acc_to_end[n]) {
+                repairs.push(Repair {
+                    pc,
+                    replacement,
+                    acc: acc + rest,
+                });
+            }
+        }
+        acc += acc_change(instruction);
+        match next(pc, instruction) {
+            Some(n) => pc = n,
+            None => break,
+        }
+    }
+    repairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(input: &str) -> Vec<Instruction> {
+        parse_program(&Text::new(8, input), input).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "nop +0\nacc -12\njmp +4";
+        let prog = program(input);
+        assert_eq!(prog[1], Instruction::new(Op::Acc, -12));
+        let lines: Vec<_> = prog.iter().map(|i| i.to_string()).collect();
+        assert_eq!(lines.join("\n"), input);
+
+        let input = "nop +0\nmul +3";
+        let error = parse_program(&Text::new(8, input), input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "an operation (`nop`, `acc`, `jmp`)");
+
+        let input = "acc 3";
+        let error = parse_program(&Text::new(8, input), input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+    }
+
+    #[test]
+    fn test_statuses() {
+        let prog = program("acc +1\njmp +1\nacc +2");
+        let mut machine = Machine::new(&prog);
+        assert_eq!(machine.step(), Status::Running);
+        assert_eq!(machine.pc, 1);
+        assert_eq!(machine.run(), Status::Terminated);
+        assert_eq!(machine.registers.acc, 3);
+        assert_eq!(machine.step(), Status::Terminated);
+
+        let prog = program("acc +1\njmp -1");
+        let mut machine = Machine::new(&prog);
+        assert_eq!(machine.run(), Status::InfiniteLoop { pc: 0 });
+        assert_eq!(machine.registers.acc, 1);
+
+        let prog = program("nop +0\njmp -2");
+        assert_eq!(Machine::new(&prog).run(), Status::OutOfBounds { pc: -1 });
+        let prog = program("jmp +3\nnop +0");
+        assert_eq!(Machine::new(&prog).run(), Status::OutOfBounds { pc: 3 });
+    }
+
+    #[test]
+    fn test_repairs() {
+        let prog =
+            program("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6");
+        assert_eq!(
+            repairs(&prog),
+            vec![Repair {
+                pc: 7,
+                replacement: Instruction::new(Op::Nop, -4),
+                acc: 8
+            }]
+        );
+
+        // Changing the first `nop` jumps to the end, changing the `jmp` continues with `acc +2`
+        let prog = program("nop +5\nacc +1\njmp +0\nacc +2\nnop +0");
+        let pcs: Vec<_> = repairs(&prog).iter().map(|r| (r.pc, r.acc)).collect();
+        assert_eq!(pcs, vec![(0, 0), (2, 3)]);
+
+        assert_eq!(repairs(&program("acc +1\nnop +0")), vec![]);
+    }
+}
@@ -0,0 +1,72 @@
+//! https://adventofcode.com/2020/day/1
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use itertools::enumerate;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(numbers: &Self::Input) -> Self::Answer1 {
+        solve(numbers)
+    }
+
+    fn part2(numbers: &Self::Input) -> Self::Answer2 {
+        solve2(numbers)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    let text = Text::new(Day01::DAY, input);
+    text.lines()
+        .map(|line| text.parse(line, "a number"))
+        .collect()
+}
+
+pub fn solve(numbers: &[u64]) -> u64 {
+    for (i, a) in enumerate(numbers) {
+        for b in &numbers[i + 1..] {
+            if a + b == 2020 {
+                return a * b;
+            }
+        }
+    }
+    0
+}
+
+pub fn solve2(numbers: &[u64]) -> u64 {
+    for (i, a) in enumerate(numbers) {
+        for (j, b) in enumerate(&numbers[i + 1..]) {
+            for c in &numbers[i + j + 1..] {
+                if a + b + c == 2020 {
+                    return a * b * c;
+                }
+            }
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(solve(&[1721, 979, 366, 299, 675, 1456]), 514579);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(solve2(&[1721, 979, 366, 299, 675, 1456]), 241861950);
+    }
+}
@@ -0,0 +1,85 @@
+//! https://adventofcode.com/2020/day/2
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+
+pub type Entry = (char, usize, usize, String);
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    type Input = Vec<Entry>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(entries: &Self::Input) -> Self::Answer1 {
+        solve(entries)
+    }
+
+    fn part2(entries: &Self::Input) -> Self::Answer2 {
+        solve2(entries)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let text = Text::new(Day02::DAY, input);
+    text.lines().map(|line| parse_line(&text, line)).collect()
+}
+
+// 5-6 s: zssmssbsms
+fn parse_line(text: &Text, line: &str) -> Result<Entry, ParseError> {
+    let (policy, password) = text.split_once(line, ": ")?;
+    let (range, letter) = text.split_once(policy, " ")?;
+    let (from, to) = text.split_once(range, "-")?;
+    let from: usize = text.parse(from, "a position")?;
+    let to: usize = text.parse(to, "a position")?;
+    let c = text.parse(letter, "a single letter")?;
+    if from == 0 || to > password.len() {
+        return Err(text.error(range, "positions within the password"));
+    }
+    Ok((c, from, to, password.to_string()))
+}
+
+pub fn solve(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .filter(|(c, from, to, pw)| {
+            let count = pw.chars().filter(|ch| ch == c).count();
+            (*from..=*to).contains(&count)
+        })
+        .count()
+}
+
+pub fn solve2(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .filter(|(c, from, to, pw)| {
+            (pw.as_bytes()[from - 1] == *c as u8) ^ (pw.as_bytes()[to - 1] == *c as u8)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("5-6 s: zssmssbsms"),
+            Ok(vec![('s', 5, 6, "zssmssbsms".to_string())])
+        );
+
+        let error = parse("1-3 a: abcde\n5-x s: zssmssbsms").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a position");
+
+        let error = parse("5-6 s zssmssbsms").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 17));
+    }
+}
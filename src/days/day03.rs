@@ -0,0 +1,88 @@
+//! https://adventofcode.com/2020/day/3
+
+use crate::grid::Grid;
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    type Input = Grid<bool>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(map: &Self::Input) -> Self::Answer1 {
+        solve(map)
+    }
+
+    fn part2(map: &Self::Input) -> Self::Answer2 {
+        solve2(map)
+    }
+}
+
+/// Parses the map with `true` for trees.
+pub fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+    let text = Text::new(Day03::DAY, input);
+    Grid::parse(&text, input, "`.` or `#`", |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })
+}
+
+pub fn solve(map: &Grid<bool>) -> i64 {
+    trees(map, 3, 1)
+}
+
+pub fn solve2(map: &Grid<bool>) -> i64 {
+    trees(map, 1, 1) * trees(map, 3, 1) * trees(map, 5, 1) * trees(map, 7, 1) * trees(map, 1, 2)
+}
+
+fn trees(map: &Grid<bool>, right: usize, down: usize) -> i64 {
+    let mut count = 0;
+    let mut x = 0;
+    let mut y = 0;
+    while y < map.height() {
+        if *map.get_wrapping(x as isize, y as isize) {
+            count += 1;
+        }
+        x += right;
+        y += down;
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+        let map = parse(input).unwrap();
+        assert_eq!(trees(&map, 3, 1), 7);
+    }
+
+    #[test]
+    fn test_empty() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a map");
+    }
+}
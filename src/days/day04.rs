@@ -0,0 +1,156 @@
+//! https://adventofcode.com/2020/day/4
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use std::collections::HashMap;
+
+pub type Passport = HashMap<String, String>;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(passports: &Self::Input) -> Self::Answer1 {
+        solve(passports)
+    }
+
+    fn part2(passports: &Self::Input) -> Self::Answer2 {
+        solve2(passports)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Passport>, ParseError> {
+    let text = Text::new(Day04::DAY, input);
+    let strings = input.split("\n\n");
+
+    let mut passports = Vec::new();
+    for s in strings {
+        let fields = s.split_whitespace();
+        let mut values = HashMap::new();
+        for field in fields {
+            let (key, value) = text.split_once(field, ":")?;
+            values.insert(key.to_string(), value.to_string());
+        }
+
+        passports.push(values);
+    }
+    Ok(passports)
+}
+
+pub fn solve(passports: &[Passport]) -> usize {
+    passports.iter().filter(|p| has_fields(p)).count()
+}
+
+pub fn solve2(passports: &[Passport]) -> usize {
+    passports
+        .iter()
+        .filter(|p| has_fields(p) && is_valid(p))
+        .count()
+}
+
+fn has_fields(passport: &Passport) -> bool {
+    ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
+        .iter()
+        .all(|key| passport.contains_key(*key))
+}
+
+// byr (Birth Year) - four digits; at least 1920 and at most 2002.
+// iyr (Issue Year) - four digits; at least 2010 and at most 2020.
+// eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
+// hgt (Height) - a number followed by either cm or in:
+//     If cm, the number must be at least 150 and at most 193.
+//     If in, the number must be at least 59 and at most 76.
+// hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
+// ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+// pid (Passport ID) - a nine-digit number, including leading zeroes.
+fn is_valid(passport: &Passport) -> bool {
+    let year = passport["byr"].parse::<u32>().unwrap_or(0);
+    if !(1920..=2002).contains(&year) {
+        return false;
+    }
+    let year = passport["iyr"].parse::<u32>().unwrap_or(0);
+    if !(2010..=2020).contains(&year) {
+        return false;
+    }
+    let year = passport["eyr"].parse::<u32>().unwrap_or(0);
+    if !(2020..=2030).contains(&year) {
+        return false;
+    }
+    let hgt = &passport["hgt"];
+    if hgt.ends_with("cm") {
+        let cm = hgt.strip_suffix("cm").unwrap().parse::<u32>().unwrap_or(0);
+        if !(150..=193).contains(&cm) {
+            return false;
+        }
+    } else if hgt.ends_with("in") {
+        let inches = hgt.strip_suffix("in").unwrap().parse::<u32>().unwrap_or(0);
+        if !(59..=76).contains(&inches) {
+            return false;
+        }
+    } else {
+        return false;
+    }
+
+    let hcl = &passport["hcl"];
+    if !hcl.starts_with('#') || hcl.len() != 7 {
+        return false;
+    }
+    if !hcl[1..].chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) {
+        return false;
+    }
+
+    let ecl = &passport["ecl"];
+    if !(ecl == "amb"
+        || ecl == "blu"
+        || ecl == "brn"
+        || ecl == "gry"
+        || ecl == "grn"
+        || ecl == "hzl"
+        || ecl == "oth")
+    {
+        return false;
+    }
+
+    let pid = &passport["pid"];
+    if pid.len() != 9 {
+        return false;
+    }
+    if !pid.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+        let passports = parse(input).unwrap();
+        assert_eq!(solve(&passports), 2);
+        assert_eq!(solve2(&passports), 2);
+    }
+}
@@ -0,0 +1,118 @@
+//! https://adventofcode.com/2020/day/5
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ids: &Self::Input) -> Self::Answer1 {
+        solve(ids)
+    }
+
+    fn part2(ids: &Self::Input) -> Self::Answer2 {
+        solve2(ids)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    let text = Text::new(Day05::DAY, input);
+    let ids = text
+        .lines()
+        .map(|line| {
+            for (i, (c, part)) in text.chars(line).enumerate() {
+                match (i, c) {
+                    (0..=6, 'F') | (0..=6, 'B') | (7..=9, 'L') | (7..=9, 'R') => {}
+                    (0..=6, _) => return Err(text.error(part, "`F` or `B`")),
+                    (7..=9, _) => return Err(text.error(part, "`L` or `R`")),
+                    _ => return Err(text.error(part, "end of line after 10 characters")),
+                }
+            }
+            if line.len() < 10 {
+                return Err(text.error_after(line, "10 characters like `FBFBBFFRLR`"));
+            }
+            Ok(seat(line))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if ids.is_empty() {
+        return Err(text.error_at_end("a boarding pass like `FBFBBFFRLR`"));
+    }
+    Ok(ids)
+}
+
+pub fn solve(ids: &[u32]) -> u32 {
+    *ids.iter().max().unwrap()
+}
+
+pub fn solve2(ids: &[u32]) -> u32 {
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.windows(2)
+        .find(|pair| pair[0] + 1 != pair[1])
+        .map_or(0, |pair| pair[0] + 1)
+}
+
+/// FBFBBFFRLR -> 357
+fn seat(s: &str) -> u32 {
+    let mut low = 0;
+    let mut high = 127;
+
+    for c in s[0..7].chars() {
+        match c {
+            'F' => {
+                high = low + ((high - low) / 2);
+            }
+            'B' => {
+                low = low + ((high - low) / 2) + 1;
+            }
+            _ => panic!("Unknown row code {}", c),
+        }
+    }
+    let row = low;
+
+    let mut low = 0;
+    let mut high = 7;
+    for c in s[7..].chars() {
+        match c {
+            'L' => {
+                high = low + ((high - low) / 2);
+            }
+            'R' => {
+                low = low + ((high - low) / 2) + 1;
+            }
+            _ => panic!("Unknown column code {}", c),
+        }
+    }
+    let column = low;
+
+    row * 8 + column
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(seat("FBFBBFFRLR"), 357);
+        assert_eq!(seat("BFFFBBFRRR"), 567);
+        assert_eq!(seat("FFFBBBFRRR"), 119);
+        assert_eq!(seat("BBFFBBFRLL"), 820);
+    }
+
+    #[test]
+    fn test_empty() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a boarding pass like `FBFBBFFRLR`");
+    }
+}
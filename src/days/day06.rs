@@ -0,0 +1,95 @@
+//! https://adventofcode.com/2020/day/6
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use std::collections::HashSet;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        solve2(input)
+    }
+}
+
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    let text = Text::new(Day06::DAY, input);
+    for line in text.lines() {
+        if let Some((_, part)) = text.chars(line).find(|(c, _)| !c.is_ascii_lowercase()) {
+            return Err(text.error(part, "a question from `a` to `z`"));
+        }
+    }
+    Ok(input.to_string())
+}
+
+pub fn solve(s: &str) -> usize {
+    let groups = s.split("\n\n");
+    let mut count = 0;
+    for group in groups {
+        let mut answers = HashSet::new();
+        for person in group.lines() {
+            for answer in person.chars() {
+                answers.insert(answer);
+            }
+        }
+        count += answers.len();
+    }
+    count
+}
+
+pub fn solve2(s: &str) -> usize {
+    let groups = s.split("\n\n");
+    let mut count = 0;
+    for group in groups {
+        let mut answers = HashSet::new();
+        for (i, person) in group.lines().enumerate() {
+            let person_answers: HashSet<_> = person.chars().collect();
+            if i != 0 {
+                answers = answers.intersection(&person_answers).copied().collect();
+            } else {
+                answers = person_answers;
+            }
+        }
+        count += answers.len();
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let s = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+        assert_eq!(solve(s), 11);
+        assert_eq!(solve2(s), 6);
+    }
+}
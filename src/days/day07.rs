@@ -0,0 +1,398 @@
+//! https://adventofcode.com/2020/day/7
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use itertools::Itertools;
+use petgraph::graphmap::DiGraphMap;
+use petgraph::visit::DfsPostOrder;
+use petgraph::Direction;
+use std::collections::{HashMap, HashSet};
+use std::iter;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    type Input = Rules;
+    type Answer1 = usize;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(rules: &Self::Input) -> Self::Answer1 {
+        solve(&rules.containers, "shiny gold")
+    }
+
+    fn part2(rules: &Self::Input) -> Self::Answer2 {
+        solve2(&rules.contents, "shiny gold").expect("Too many bags to count in a u128")
+    }
+}
+
+/// The rules in both directions, for part 1 and part 2. Parsing checks that no bag contains
+/// itself.
+pub struct Rules {
+    /// For a bag color, the bags that can contain it.
+    containers: HashMap<String, Vec<Bag>>,
+    /// For a bag color, the bags it contains.
+    contents: HashMap<String, Vec<Bag>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Bag {
+    color: String,
+    count: u64,
+}
+
+pub fn parse(rules: &str) -> Result<Rules, ParseError> {
+    let rules = parse_lines(rules)?;
+    Ok(Rules {
+        containers: containers(&rules),
+        contents: contents(&rules),
+    })
+}
+
+/// A rule like `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+struct Rule<'a> {
+    container: &'a str,
+    contents: Vec<(u64, &'a str)>,
+}
+
+fn parse_lines(rules: &str) -> Result<Vec<Rule<'_>>, ParseError> {
+    let text = Text::new(Day07::DAY, rules);
+    let rules = text
+        .lines()
+        .map(|line| {
+            let (container, inside) = text.split_once(line, " bags contain ")?;
+            let inside = text.strip_suffix(inside, ".")?;
+            let mut contents = Vec::new();
+            if inside != "no other bags" {
+                for bag in inside.split(", ") {
+                    let (count, color) = text.split_once(bag, " ")?;
+                    let count = text.parse(count, "a number of bags")?;
+                    let color = match color.strip_suffix(" bags") {
+                        Some(color) => color,
+                        None => text.strip_suffix(color, " bag")?,
+                    };
+                    contents.push((count, color));
+                }
+            }
+            Ok(Rule {
+                container,
+                contents,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    check_cycles(&text, &rules)?;
+    Ok(rules)
+}
+
+/// Fails if a bag contains itself, directly or through other bags, as it would then contain
+/// infinitely many bags. The error is at the bag that closes the cycle, and names the cycle.
+fn check_cycles(text: &Text<'_>, rules: &[Rule<'_>]) -> Result<(), ParseError> {
+    let contents: HashMap<&str, &[(u64, &str)]> = rules
+        .iter()
+        .map(|rule| (rule.container, rule.contents.as_slice()))
+        .collect();
+    // Bags that are known not to be part of a cycle
+    let mut done = HashSet::new();
+    for rule in rules {
+        if done.contains(rule.container) {
+            continue;
+        }
+        // Depth-first search without recursion, with the index of the next bag to look at for
+        // every bag on the path
+        let mut path = vec![(rule.container, 0)];
+        let mut on_path: HashSet<&str> = iter::once(rule.container).collect();
+        while let Some(&(bag, next)) = path.last() {
+            match contents.get(bag).and_then(|inside| inside.get(next)) {
+                Some(&(_, color)) => {
+                    path.last_mut().unwrap().1 += 1;
+                    if on_path.contains(color) {
+                        let start = path.iter().position(|&(b, _)| b == color).unwrap();
+                        let cycle = path[start..]
+                            .iter()
+                            .map(|&(b, _)| b)
+                            .chain(iter::once(color))
+                            .join(" -> ");
+                        return Err(text.error(
+                            color,
+                            &format!("a bag that doesn't contain itself ({})", cycle),
+                        ));
+                    }
+                    if !done.contains(color) {
+                        on_path.insert(color);
+                        path.push((color, 0));
+                    }
+                }
+                None => {
+                    done.insert(bag);
+                    on_path.remove(bag);
+                    path.pop();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_rules(rules: &str) -> Result<HashMap<String, Vec<Bag>>, ParseError> {
+    Ok(containers(&parse_lines(rules)?))
+}
+
+pub fn parse_rules2(rules: &str) -> Result<HashMap<String, Vec<Bag>>, ParseError> {
+    Ok(contents(&parse_lines(rules)?))
+}
+
+fn containers(rules: &[Rule<'_>]) -> HashMap<String, Vec<Bag>> {
+    let mut result: HashMap<String, Vec<Bag>> = HashMap::new();
+    for rule in rules {
+        for &(count, color) in &rule.contents {
+            let bag = Bag {
+                color: rule.container.to_string(),
+                count,
+            };
+            result.entry(color.to_string()).or_default().push(bag);
+        }
+    }
+    result
+}
+
+fn contents(rules: &[Rule<'_>]) -> HashMap<String, Vec<Bag>> {
+    let mut result: HashMap<String, Vec<Bag>> = HashMap::new();
+    for rule in rules {
+        for &(count, color) in &rule.contents {
+            let bag = Bag {
+                color: color.to_string(),
+                count,
+            };
+            result
+                .entry(rule.container.to_string())
+                .or_default()
+                .push(bag);
+        }
+    }
+    result
+}
+
+pub fn solve(rules: &HashMap<String, Vec<Bag>>, start: &str) -> usize {
+    let mut visited = HashSet::new();
+    visited.insert(start.to_string());
+
+    let mut work = vec![start.to_string()];
+
+    while let Some(s) = work.pop() {
+        if let Some(containers) = rules.get(&s) {
+            for container in containers {
+                if !visited.contains(&container.color) {
+                    visited.insert(container.color.clone());
+                    work.push(container.color.clone());
+                }
+            }
+        }
+    }
+    visited.len() - 1
+}
+
+/// The number of bags inside a bag of the color, `None` if that doesn't fit in a `u128`. The rules
+/// must not contain cycles, which parsing checks.
+///
+/// Visits the bags in post-order without recursion, so that deep nestings don't overflow the
+/// stack. Remembers the count for every color, as bags inside different bags often contain the
+/// same.
+pub fn solve2(rules: &HashMap<String, Vec<Bag>>, start: &str) -> Option<u128> {
+    let mut counts: HashMap<&str, u128> = HashMap::new();
+    // A bag is pushed again after the bags inside it, and counted when it comes up the second time
+    let mut stack = vec![(start, false)];
+    while let Some((color, inside_counted)) = stack.pop() {
+        if counts.contains_key(color) {
+            continue;
+        }
+        let bags = rules.get(color).map_or(&[][..], Vec::as_slice);
+        if inside_counted {
+            let count = bags.iter().try_fold(0u128, |count, bag| {
+                // The bag itself and everything inside it
+                let each = counts[bag.color.as_str()].checked_add(1)?;
+                count.checked_add(each.checked_mul(bag.count.into())?)
+            })?;
+            counts.insert(color, count);
+        } else {
+            stack.push((color, true));
+            stack.extend(bags.iter().map(|bag| (bag.color.as_str(), false)));
+        }
+    }
+    Some(counts[start])
+}
+
+/// Bag color to node index, and the graph of which bag contains how many of which other bag.
+pub type RulesGraph = (HashMap<String, usize>, DiGraphMap<usize, u64>);
+
+/// Alternative solution using a graph library instead of constructing two different
+/// `HashMap<String, Vec<Bag>>` for part 1 and 2.
+pub fn parse_rules_graph(rules: &str) -> Result<RulesGraph, ParseError> {
+    let mut names = HashMap::new();
+    let mut graph = DiGraphMap::new();
+    for rule in parse_lines(rules)? {
+        let len = names.len();
+        let container_index = *names.entry(rule.container.to_string()).or_insert(len);
+
+        for (count, color) in rule.contents {
+            let len = names.len();
+            let color_index = *names.entry(color.to_string()).or_insert(len);
+
+            graph.add_node(container_index);
+            graph.add_node(color_index);
+            graph.add_edge(container_index, color_index, count);
+        }
+    }
+    Ok((names, graph))
+}
+
+pub fn solve_graph(rules: &DiGraphMap<usize, u64>, start: usize) -> usize {
+    let mut visited = HashSet::new();
+    visited.insert(start);
+
+    let mut work = vec![start];
+
+    while let Some(s) = work.pop() {
+        for node in rules.neighbors_directed(s, Direction::Incoming) {
+            if !visited.contains(&node) {
+                visited.insert(node);
+                work.push(node);
+            }
+        }
+    }
+    visited.len() - 1
+}
+
+/// Like [`solve2`], visiting the bags in post-order so that the bags inside a bag are counted
+/// before it.
+pub fn solve_graph2(rules: &DiGraphMap<usize, u64>, start: usize) -> Option<u128> {
+    if !rules.contains_node(start) {
+        return Some(0);
+    }
+    let mut counts: HashMap<usize, u128> = HashMap::new();
+    let mut dfs = DfsPostOrder::new(rules, start);
+    while let Some(bag) = dfs.next(rules) {
+        let count = rules.edges(bag).try_fold(0u128, |count, (_, inside, &n)| {
+            let each = counts[&inside].checked_add(1)?;
+            count.checked_add(each.checked_mul(n.into())?)
+        })?;
+        counts.insert(bag, count);
+    }
+    Some(counts[&start])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_1() {
+        let s = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+        let rules = parse_rules(s).unwrap();
+        assert_eq!(solve(&rules, "shiny gold"), 4);
+
+        let (names, graph) = parse_rules_graph(s).unwrap();
+        assert_eq!(solve_graph(&graph, names["shiny gold"]), 4);
+    }
+
+    #[test]
+    fn test_example_2() {
+        let s = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+";
+
+        let rules = parse_rules2(s).unwrap();
+        assert_eq!(solve2(&rules, "shiny gold"), Some(126));
+        assert_eq!(solve2(&rules, "dark violet"), Some(0));
+
+        let (names, graph) = parse_rules_graph(s).unwrap();
+        assert_eq!(solve_graph2(&graph, names["shiny gold"]), Some(126));
+    }
+
+    /// Every bag contains two bags of the next level, in two ways, so that there are 2^levels
+    /// ways to get to the last level.
+    fn nested(levels: usize) -> String {
+        let mut rules = String::new();
+        for i in 0..levels {
+            for color in &["a", "b"] {
+                rules.push_str(&format!(
+                    "{0} {1} bags contain 1 a {2} bag, 1 b {2} bag.\n",
+                    color,
+                    i,
+                    i + 1
+                ));
+            }
+        }
+        rules
+    }
+
+    #[test]
+    fn test_shared_bags() {
+        // 2 + 4 + ... + 2^levels bags
+        let s = nested(100);
+        let rules = parse_rules2(&s).unwrap();
+        assert_eq!(solve2(&rules, "a 0"), Some((1 << 101) - 2));
+        let (names, graph) = parse_rules_graph(&s).unwrap();
+        assert_eq!(solve_graph2(&graph, names["a 0"]), Some((1 << 101) - 2));
+
+        let s = nested(130);
+        assert_eq!(solve2(&parse_rules2(&s).unwrap(), "a 0"), None);
+        let (names, graph) = parse_rules_graph(&s).unwrap();
+        assert_eq!(solve_graph2(&graph, names["a 0"]), None);
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let levels = 20_000;
+        let s = (0..levels)
+            .map(|i| format!("level {} bags contain 1 level {} bag.\n", i, i + 1))
+            .collect::<String>();
+        let rules = parse(&s).unwrap();
+        assert_eq!(solve2(&rules.contents, "level 0"), Some(levels));
+        assert_eq!(
+            solve(&rules.containers, &format!("level {}", levels)),
+            levels as usize
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let s = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 1 dark blue bag, 2 dark orange bags.
+dark blue bags contain no other bags.
+dark orange bags contain 3 shiny gold bags.";
+        let error = parse(s).err().unwrap();
+        assert_eq!((error.line, error.column), (4, 28));
+        assert_eq!(
+            error.to_string(),
+            "Day 7, line 4, column 28: expected a bag that doesn't contain itself \
+             (shiny gold -> dark red -> dark orange -> shiny gold), found \"shiny gold\""
+        );
+        assert!(parse_rules_graph(s).is_err());
+
+        let error = parse("pale blue bags contain 1 pale blue bag.")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.expected,
+            "a bag that doesn't contain itself (pale blue -> pale blue)"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! https://adventofcode.com/2020/day/8
+
+use crate::console::{parse_program, repairs, Instruction, Machine, Status};
+use crate::parse::{ParseError, Text};
+use crate::{Answer, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    type Input = Vec<Instruction>;
+    type Answer1 = Answer<i64>;
+    type Answer2 = Answer<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(prog: &Self::Input) -> Self::Answer1 {
+        solve(prog)
+    }
+
+    fn part2(prog: &Self::Input) -> Self::Answer2 {
+        solve2(prog)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let text = Text::new(Day08::DAY, input);
+    let prog = parse_program(&text, input)?;
+    if prog.is_empty() {
+        return Err(text.error_at_end("an instruction"));
+    }
+    Ok(prog)
+}
+
+/// The accumulator right before an instruction would be executed a second time.
+pub fn solve(prog: &[Instruction]) -> Answer<i64> {
+    let mut machine = Machine::new(prog);
+    match machine.run() {
+        Status::InfiniteLoop { .. } => Answer::Solved(machine.registers.acc),
+        status => Answer::Unsolved(format!("the program doesn't loop, it stopped: {}", status)),
+    }
+}
+
+/// The accumulator after the program terminates with one `nop` changed to `jmp` or vice versa.
+/// There is no answer if no change or more than one makes it terminate, the latter lists them.
+pub fn solve2(prog: &[Instruction]) -> Answer<i64> {
+    let repairs = repairs(prog);
+    match repairs.as_slice() {
+        [repair] => Answer::Solved(repair.acc),
+        [] if Machine::new(prog).run() == Status::Terminated => {
+            Answer::Unsolved("the program terminates without a change".to_string())
+        }
+        [] => Answer::Unsolved("no change makes the program terminate".to_string()),
+        repairs => Answer::Unsolved(format!(
+            "{} changes make the program terminate: {}",
+            repairs.len(),
+            repairs
+                .iter()
+                .map(|r| format!("line {} gives {}", r.pc + 1, r.acc))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+        let prog = parse(input).unwrap();
+        assert_eq!(solve(&prog), Answer::Solved(5));
+        assert_eq!(solve2(&prog), Answer::Solved(8));
+    }
+
+    #[test]
+    fn test_unusable() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "an instruction");
+
+        let prog = parse("nop +0\nacc +1").unwrap();
+        assert_eq!(
+            solve(&prog).to_string(),
+            "no answer: the program doesn't loop, it stopped: terminated"
+        );
+        assert_eq!(
+            solve2(&prog),
+            Answer::Unsolved("the program terminates without a change".to_string())
+        );
+
+        let prog = parse("jmp +0\njmp -1").unwrap();
+        assert_eq!(solve(&prog), Answer::Solved(0));
+        assert_eq!(
+            solve2(&prog),
+            Answer::Unsolved("no change makes the program terminate".to_string())
+        );
+
+        let prog = parse("nop +2\njmp +0\nacc +1").unwrap();
+        assert_eq!(
+            solve2(&prog),
+            Answer::Unsolved(
+                "2 changes make the program terminate: line 1 gives 1, line 2 gives 1".to_string()
+            )
+        );
+    }
+}
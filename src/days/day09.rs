@@ -0,0 +1,78 @@
+//! https://adventofcode.com/2020/day/9
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use itertools::Itertools;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Self::Answer1 {
+        solve(nums, 25)
+    }
+
+    fn part2(nums: &Self::Input) -> Self::Answer2 {
+        solve2(nums, solve(nums, 25))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    let text = Text::new(Day09::DAY, input);
+    text.lines().map(|l| text.parse(l, "a number")).collect()
+}
+
+pub fn solve(nums: &[u64], preamble: usize) -> u64 {
+    for window in nums.windows(preamble + 1) {
+        let last = window[window.len() - 1];
+        let before = &window[0..window.len() - 1];
+        if !before
+            .iter()
+            .tuple_combinations()
+            .any(|(a, b)| a + b == last)
+        {
+            return last;
+        }
+    }
+    0
+}
+
+pub fn solve2(nums: &[u64], target: u64) -> u64 {
+    for i in 0..nums.len() {
+        let mut sum = 0;
+        // There's probably a better algorithm, but this quadratic one was good enough.
+        for j in i..nums.len() {
+            sum += &nums[j];
+            if sum == target {
+                // Could remember smallest and largest while summing, but this was easier.
+                return nums[i..j].iter().min().unwrap() + nums[i..j].iter().max().unwrap();
+            } else if sum > target {
+                break;
+            }
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let nums = vec![
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
+            576,
+        ];
+        assert_eq!(solve(&nums, 5), 127);
+        assert_eq!(solve2(&nums, 127), 62);
+    }
+}
@@ -0,0 +1,128 @@
+//! https://adventofcode.com/2020/day/10
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use std::collections::HashMap;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Self::Answer1 {
+        solve(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Self::Answer2 {
+        solve2(nums)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    let text = Text::new(Day10::DAY, input);
+    let lines: Vec<_> = text.lines().collect();
+    let nums = lines
+        .iter()
+        .map(|l| text.parse(l, "a number"))
+        .collect::<Result<Vec<u64>, _>>()?;
+    if nums.is_empty() {
+        return Err(text.error_at_end("a joltage rating"));
+    }
+    let mut sorted: Vec<_> = (0..nums.len()).collect();
+    sorted.sort_by_key(|&i| nums[i]);
+    let mut previous = 0;
+    for i in sorted {
+        if nums[i] > previous + 3 {
+            return Err(text.error(
+                lines[i],
+                &format!("an adapter at most 3 jolts above {}", previous),
+            ));
+        }
+        previous = nums[i];
+    }
+    Ok(nums)
+}
+
+pub fn solve(nums: &[u64]) -> u64 {
+    let mut nums = nums.to_vec();
+    nums.sort();
+    nums.insert(0, 0);
+    let mut diffs = HashMap::new();
+    diffs.insert(3, 1);
+    for window in nums.windows(2) {
+        let diff = window[1] - window[0];
+        *diffs.entry(diff).or_insert(0) += 1;
+    }
+    diffs.get(&1).copied().unwrap_or(0) * diffs[&3]
+}
+
+pub fn solve2(nums: &[u64]) -> u64 {
+    let mut nums = nums.to_vec();
+    nums.sort();
+    nums.insert(0, 0);
+
+    let mut memo = HashMap::new();
+    arrangements(&nums, &mut memo)
+}
+
+fn arrangements(nums: &[u64], memo: &mut HashMap<usize, u64>) -> u64 {
+    if nums.len() == 1 {
+        return 1;
+    }
+
+    if let Some(result) = memo.get(&nums.len()) {
+        return *result;
+    }
+
+    let mut count = 0;
+    let first = &nums[0];
+    for i in 1..nums.len() {
+        if nums[i] - first <= 3 {
+            count += arrangements(&nums[i..], memo);
+        } else {
+            break;
+        }
+    }
+
+    memo.insert(nums.len(), count);
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let first = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        let second = vec![
+            28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45, 19, 38, 39, 11, 1, 32, 25, 35,
+            8, 17, 7, 9, 4, 2, 34, 10, 3,
+        ];
+        assert_eq!(solve(&second), 220);
+
+        assert_eq!(solve2(&first), 8);
+        assert_eq!(solve2(&second), 19208);
+    }
+
+    #[test]
+    fn test_unusable() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a joltage rating");
+
+        let error = parse("1\n9\n4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "an adapter at most 3 jolts above 4");
+
+        assert_eq!(solve(&parse("3").unwrap()), 0);
+    }
+}
@@ -0,0 +1,162 @@
+//! https://adventofcode.com/2020/day/11
+
+use crate::automaton::{Automaton, Rule};
+use crate::grid::{Grid, Pos, DIRECTIONS_8};
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use reformation::Reformation;
+use std::collections::HashMap;
+use std::fmt;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Grid<Seat>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(seats: &Self::Input) -> Self::Answer1 {
+        solve(seats.clone())
+    }
+
+    fn part2(seats: &Self::Input) -> Self::Answer2 {
+        solve2(seats.clone())
+    }
+}
+
+#[derive(Reformation, Clone, Debug, Eq, PartialEq)]
+pub enum Seat {
+    #[reformation("L")]
+    Empty,
+    #[reformation("#")]
+    Occupied,
+    #[reformation(".")]
+    Floor,
+}
+
+pub fn parse(input: &str) -> Result<Grid<Seat>, ParseError> {
+    let text = Text::new(Day11::DAY, input);
+    Grid::parse(&text, input, "`L`, `#` or `.`", |c| {
+        Seat::parse(c.encode_utf8(&mut [0; 4])).ok()
+    })
+}
+
+pub fn solve(grid: Grid<Seat>) -> usize {
+    simulate(grid, adjacent_seats, 4)
+}
+
+pub fn solve2(grid: Grid<Seat>) -> usize {
+    simulate(grid, visible_seats, 5)
+}
+
+/// Applies the seating rules until nothing changes, and returns the number of occupied seats.
+/// Only seats are cells of the automaton, with occupied seats being live. `neighbours` returns the
+/// seats that affect a seat.
+fn simulate(
+    grid: Grid<Seat>,
+    neighbours: fn(&Grid<Seat>, Pos) -> Vec<Pos>,
+    tolerance: usize,
+) -> usize {
+    let seats: Vec<Pos> = grid
+        .iter()
+        .filter(|(_, seat)| **seat != Seat::Floor)
+        .map(|(pos, _)| pos)
+        .collect();
+    let indices: HashMap<Pos, usize> = seats.iter().enumerate().map(|(i, &pos)| (pos, i)).collect();
+
+    // The neighbours of all seats by index, the ones of seat `i` are at `offsets[i]..offsets[i + 1]`
+    let mut offsets = vec![0];
+    let mut seat_neighbours = Vec::new();
+    for &pos in &seats {
+        seat_neighbours.extend(neighbours(&grid, pos).iter().map(|p| indices[p]));
+        offsets.push(seat_neighbours.len());
+    }
+    let occupied = seats
+        .iter()
+        .enumerate()
+        .filter(|(_, &pos)| grid[pos] == Seat::Occupied)
+        .map(|(i, _)| i)
+        .collect();
+
+    // An empty seat becomes occupied if no seat it affects is occupied, an occupied seat
+    // becomes empty if `tolerance` or more are occupied.
+    let rule = Rule::new(vec![0], 0..tolerance);
+    let automaton = Automaton::new(
+        |&i: &usize| seat_neighbours[offsets[i]..offsets[i + 1]].iter().copied(),
+        rule,
+    )
+    .within(0..seats.len());
+    automaton.run_to_fixed_point(occupied).live.len()
+}
+
+/// The first seat in each direction.
+fn visible_seats(grid: &Grid<Seat>, pos: Pos) -> Vec<Pos> {
+    DIRECTIONS_8
+        .iter()
+        .filter_map(|&direction| {
+            grid.ray(pos, direction)
+                .find(|(_, seat)| **seat != Seat::Floor)
+                .map(|(p, _)| p)
+        })
+        .collect()
+}
+
+fn adjacent_seats(grid: &Grid<Seat>, pos: Pos) -> Vec<Pos> {
+    grid.neighbours_8(pos)
+        .filter(|&p| grid[p] != Seat::Floor)
+        .collect()
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Seat::Occupied => '#',
+            Seat::Empty => 'L',
+            Seat::Floor => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL
+";
+
+        let parsed = parse(input).unwrap();
+        assert_eq!(solve(parsed.clone()), 37);
+        assert_eq!(solve2(parsed), 26);
+    }
+
+    #[test]
+    fn test_empty() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a map");
+
+        let error = parse("\nL.L").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "`L`, `#` or `.`");
+
+        // No seats at all is fine though
+        assert_eq!(solve(parse("...\n...").unwrap()), 0);
+    }
+}
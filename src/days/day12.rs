@@ -0,0 +1,157 @@
+//! https://adventofcode.com/2020/day/12
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use reformation::Reformation;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = Vec<Action>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(actions: &Self::Input) -> Self::Answer1 {
+        solve(actions)
+    }
+
+    fn part2(actions: &Self::Input) -> Self::Answer2 {
+        solve2(actions)
+    }
+}
+
+#[derive(Reformation, Clone, Debug)]
+pub enum Action {
+    #[reformation("N{}")]
+    N(i64),
+    #[reformation("S{}")]
+    S(i64),
+    #[reformation("E{}")]
+    E(i64),
+    #[reformation("W{}")]
+    W(i64),
+    #[reformation("L{}")]
+    L(i64),
+    #[reformation("R{}")]
+    R(i64),
+    #[reformation("F{}")]
+    F(i64),
+}
+
+pub fn parse(input: &str) -> Result<Vec<Action>, ParseError> {
+    let text = Text::new(Day12::DAY, input);
+    text.lines()
+        .map(|l| {
+            let action = Action::parse(l).map_err(|_| text.error(l, "an action like `F10`"))?;
+            match action {
+                Action::L(deg) | Action::R(deg) if deg < 0 || deg % 90 != 0 => {
+                    Err(text.error(&l[1..], "a multiple of 90 degrees"))
+                }
+                _ => Ok(action),
+            }
+        })
+        .collect()
+}
+
+pub fn solve(actions: &[Action]) -> i64 {
+    let mut x = 0;
+    let mut y = 0;
+    let mut direction = 90;
+    for action in actions {
+        match action {
+            Action::N(n) => {
+                y += n;
+            }
+            Action::S(n) => {
+                y -= n;
+            }
+            Action::E(n) => {
+                x += n;
+            }
+            Action::W(n) => {
+                x -= n;
+            }
+            Action::L(deg) => {
+                direction = (direction - deg + 360) % 360;
+            }
+            Action::R(deg) => {
+                direction = (direction + deg) % 360;
+            }
+            Action::F(n) => match direction {
+                0 => y += n,
+                90 => x += n,
+                180 => y -= n,
+                270 => x -= n,
+                _ => panic!("Unknown direction {}", direction),
+            },
+        }
+    }
+    x.abs() + y.abs()
+}
+
+pub fn solve2(actions: &[Action]) -> i64 {
+    let mut x = 0;
+    let mut y = 0;
+    let mut way_x = 10;
+    let mut way_y = 1;
+
+    for action in actions {
+        match action {
+            Action::N(n) => {
+                way_y += n;
+            }
+            Action::S(n) => {
+                way_y -= n;
+            }
+            Action::E(n) => {
+                way_x += n;
+            }
+            Action::W(n) => {
+                way_x -= n;
+            }
+            Action::L(deg) => {
+                for _ in 0..(deg / 90) {
+                    let new_x = -way_y;
+                    way_y = way_x;
+                    way_x = new_x;
+                }
+            }
+            Action::R(deg) => {
+                for _ in 0..(deg / 90) {
+                    let new_y = -way_x;
+                    way_x = way_y;
+                    way_y = new_y;
+                }
+            }
+            Action::F(n) => {
+                x += way_x * n;
+                y += way_y * n;
+            }
+        }
+    }
+    x.abs() + y.abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = "F10
+N3
+F7
+R90
+F11
+";
+
+        let actions = parse(input).unwrap();
+        assert_eq!(solve(&actions), 25);
+        assert_eq!(solve2(&actions), 286);
+    }
+}
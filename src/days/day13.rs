@@ -0,0 +1,164 @@
+//! https://adventofcode.com/2020/day/13
+
+use crate::modular::{crt, Congruence};
+use crate::parse::{ParseError, Text};
+use crate::{Answer, Solution};
+use num::BigInt;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Notes;
+    type Answer1 = u64;
+    type Answer2 = Answer<BigInt>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(notes: &Self::Input) -> Self::Answer1 {
+        solve(notes.departure, &notes.buses)
+    }
+
+    fn part2(notes: &Self::Input) -> Self::Answer2 {
+        solve2(&notes.buses)
+    }
+}
+
+#[derive(Debug)]
+pub struct Notes {
+    departure: u64,
+    buses: Vec<Bus>,
+}
+
+#[derive(Debug)]
+pub struct Bus {
+    minutes: u64,
+    offset: u64,
+}
+
+pub fn parse(input: &str) -> Result<Notes, ParseError> {
+    let text = Text::new(Day13::DAY, input);
+    let mut lines = text.lines();
+    let departure = match lines.next() {
+        Some(line) => text.parse(line, "a departure time")?,
+        None => return Err(text.error_at_end("a departure time")),
+    };
+    let line = lines
+        .next()
+        .ok_or_else(|| text.error_at_end("a line with bus IDs"))?;
+    let mut buses = Vec::new();
+    for (i, s) in line.split(',').enumerate() {
+        if s != "x" {
+            let minutes = text.parse::<u64>(s, "a bus ID or `x`")?;
+            if minutes == 0 {
+                return Err(text.error(s, "a bus ID greater than 0"));
+            }
+            buses.push(Bus {
+                minutes,
+                offset: i as u64,
+            });
+        }
+    }
+    if buses.is_empty() {
+        return Err(text.error(line, "at least one bus ID"));
+    }
+    Ok(Notes { departure, buses })
+}
+
+pub fn solve(departure: u64, buses: &[Bus]) -> u64 {
+    let (minutes, bus) = buses
+        .iter()
+        .map(|b| (b.minutes - (departure % b.minutes), b.minutes))
+        .min()
+        .unwrap();
+    minutes * bus
+}
+
+/// The earliest time where each bus departs at its offset after it, which solves the
+/// congruences `t + offset ≡ 0 (mod minutes)`. There is no answer if the buses contradict each
+/// other, which is possible if their IDs aren't coprime.
+pub fn solve2(buses: &[Bus]) -> Answer<BigInt> {
+    let congruences: Vec<Congruence> = buses
+        .iter()
+        .map(|b| Congruence::new((b.minutes - b.offset % b.minutes) % b.minutes, b.minutes))
+        .collect();
+    match crt(&congruences) {
+        Ok(combined) => Answer::Solved(combined.residue()),
+        Err(no_solution) => {
+            let bus = &buses[no_solution.index];
+            Answer::Unsolved(format!(
+                "bus {} can't depart {} minutes after a time that works for the buses before it",
+                bus.minutes, bus.offset
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = "939
+7,13,x,x,59,x,31,19";
+
+        let notes = parse(input).unwrap();
+        assert_eq!(solve(notes.departure, &notes.buses), 295);
+        assert_eq!(solve2(&notes.buses).to_string(), "1068781");
+
+        let solve2 = |line: &str| solve2(&parse(&format!("0\n{}", line)).unwrap().buses);
+        assert_eq!(solve2("17,x,13,19").to_string(), "3417");
+        assert_eq!(solve2("67,7,59,61").to_string(), "754018");
+        assert_eq!(solve2("67,x,7,59,61").to_string(), "779210");
+        assert_eq!(solve2("67,7,x,59,61").to_string(), "1261476");
+        assert_eq!(solve2("1789,37,47,1889").to_string(), "1202161486");
+        // Not coprime
+        assert_eq!(solve2("4,x,6").to_string(), "4");
+    }
+
+    #[test]
+    fn test_unsolvable_and_big() {
+        let notes = parse("939\n4,x,x,6").unwrap();
+        assert_eq!(
+            solve2(&notes.buses),
+            Answer::Unsolved(
+                "bus 6 can't depart 3 minutes after a time that works for the buses before it"
+                    .to_string()
+            )
+        );
+
+        // The product of the IDs doesn't fit in a u64, and neither does the answer
+        let notes = parse("0\n1000000007,1000000009,1000000021").unwrap();
+        let t = match solve2(&notes.buses) {
+            Answer::Solved(t) => t,
+            answer => panic!("Expected a solution, got {}", answer),
+        };
+        assert!(t > BigInt::from(u64::MAX));
+        for (offset, id) in [1000000007u64, 1000000009, 1000000021].iter().enumerate() {
+            assert_eq!((&t + offset) % id, BigInt::from(0));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("939\n7,13,y,59").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.expected, "a bus ID or `x`");
+        assert_eq!(error.found, "\"y\"");
+
+        let error = parse("939\n7,x,0").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.expected, "a bus ID greater than 0");
+
+        let error = parse("939\nx,x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "at least one bus ID");
+
+        let error = parse("939").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.expected, "a line with bus IDs");
+    }
+}
@@ -0,0 +1,144 @@
+//! https://adventofcode.com/2020/day/14
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use reformation::Reformation;
+use std::collections::HashMap;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = Vec<Inst>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(instructions: &Self::Input) -> Self::Answer1 {
+        solve(instructions)
+    }
+
+    fn part2(instructions: &Self::Input) -> Self::Answer2 {
+        solve2(instructions)
+    }
+}
+
+#[derive(Reformation, Clone, Debug)]
+pub enum Inst {
+    #[reformation("mask = {}")]
+    Mask(String),
+    #[reformation("mem\\[{}\\] = {}")]
+    Mem(u64, u64),
+}
+
+pub fn parse(input: &str) -> Result<Vec<Inst>, ParseError> {
+    let text = Text::new(Day14::DAY, input);
+    text.lines()
+        .map(|l| {
+            let inst =
+                Inst::parse(l).map_err(|_| text.error(l, "`mask = ...` or `mem[...] = ...`"))?;
+            if let Inst::Mask(mask) = &inst {
+                let part = &l[l.len() - mask.len()..];
+                if mask.len() != 36 {
+                    return Err(text.error(part, "a mask with 36 bits"));
+                }
+                if let Some((_, c)) = text
+                    .chars(part)
+                    .find(|(c, _)| !matches!(c, 'X' | '0' | '1'))
+                {
+                    return Err(text.error(c, "`X`, `0` or `1`"));
+                }
+            }
+            Ok(inst)
+        })
+        .collect()
+}
+
+pub fn solve(instructions: &[Inst]) -> u64 {
+    let mut on_mask = 0;
+    let mut off_mask = 0;
+    let mut memory = HashMap::new();
+    for inst in instructions {
+        match inst {
+            Inst::Mask(s) => {
+                on_mask = u64::from_str_radix(&s.replace('X', "0"), 2).unwrap();
+                off_mask = u64::from_str_radix(&s.replace('X', "1"), 2).unwrap();
+            }
+            Inst::Mem(target, mut value) => {
+                value |= on_mask;
+                value &= off_mask;
+                memory.insert(target, value);
+            }
+        }
+    }
+    memory.values().sum()
+}
+
+pub fn solve2(instructions: &[Inst]) -> u64 {
+    let mut on_mask = 0;
+    let mut floating = Vec::new();
+    let mut memory = HashMap::new();
+    for inst in instructions {
+        match inst {
+            Inst::Mask(s) => {
+                on_mask = u64::from_str_radix(&s.replace('X', "0"), 2).unwrap();
+                floating = s
+                    .chars()
+                    .rev()
+                    .enumerate()
+                    .filter(|(_i, c)| *c == 'X')
+                    .map(|(i, _c)| i)
+                    .collect();
+            }
+            Inst::Mem(mut target, value) => {
+                target |= on_mask;
+                set_floating(&mut memory, target, *value, &floating);
+            }
+        }
+    }
+    memory.values().sum()
+}
+
+fn set_floating(memory: &mut HashMap<u64, u64>, mut target: u64, value: u64, floating: &[usize]) {
+    if !floating.is_empty() {
+        let index = floating[0];
+        let mask = 1 << index;
+
+        target &= !mask;
+        memory.insert(target, value);
+        set_floating(memory, target, value, &floating[1..]);
+
+        target |= mask;
+        memory.insert(target, value);
+        set_floating(memory, target, value, &floating[1..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_part1() {
+        let input = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+        let instructions = parse(input).unwrap();
+        assert_eq!(solve(&instructions), 165);
+    }
+
+    #[test]
+    fn test_examples_part2() {
+        let input = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+";
+        let instructions = parse(input).unwrap();
+        assert_eq!(solve2(&instructions), 208);
+    }
+}
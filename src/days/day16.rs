@@ -0,0 +1,212 @@
+//! https://adventofcode.com/2020/day/16
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use reformation::Reformation;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = Puzzle;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        solve2(puzzle)
+    }
+}
+
+pub struct Puzzle {
+    conditions: Vec<Condition>,
+    your: Vec<u64>,
+    nearby: Vec<Vec<u64>>,
+}
+
+struct Condition {
+    name: String,
+    ranges: Vec<FromTo>,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{from}-{to}")]
+struct FromTo {
+    from: u64,
+    to: u64,
+}
+
+enum State {
+    Cond,
+    Your,
+    Nearby,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day16::DAY, input);
+    let mut conditions = Vec::new();
+    let mut tickets = Vec::new();
+    let mut your = Vec::new();
+    let mut state = State::Cond;
+    for line in text.lines() {
+        if line == "your ticket:" {
+            state = State::Your;
+        } else if line == "nearby tickets:" {
+            state = State::Nearby;
+        } else if !line.is_empty() {
+            match state {
+                State::Cond => {
+                    let (name, ranges) = text.split_once(line, ": ")?;
+                    let ranges = ranges
+                        .split(" or ")
+                        .map(|r| FromTo::parse(r).map_err(|_| text.error(r, "a range like `1-3`")))
+                        .collect::<Result<_, _>>()?;
+                    conditions.push(Condition {
+                        name: name.to_string(),
+                        ranges,
+                    });
+                }
+                State::Your => {
+                    your = parse_ticket(&text, line, conditions.len())?;
+                }
+                State::Nearby => {
+                    tickets.push(parse_ticket(&text, line, conditions.len())?);
+                }
+            }
+        }
+    }
+    if your.is_empty() {
+        return Err(text.error_at_end("a line `your ticket:` followed by a ticket"));
+    }
+    Ok(Puzzle {
+        conditions,
+        your,
+        nearby: tickets,
+    })
+}
+
+fn parse_ticket(text: &Text, line: &str, fields: usize) -> Result<Vec<u64>, ParseError> {
+    let values: Vec<u64> = line
+        .split(',')
+        .map(|v| text.parse(v, "a number"))
+        .collect::<Result<_, _>>()?;
+    if values.len() != fields {
+        return Err(text.error(line, &format!("{} values, one for each field", fields)));
+    }
+    Ok(values)
+}
+
+pub fn solve(puzzle: &Puzzle) -> u64 {
+    let mut valid = HashSet::new();
+
+    for condition in &puzzle.conditions {
+        for range in &condition.ranges {
+            for i in range.from..=range.to {
+                valid.insert(i);
+            }
+        }
+    }
+
+    let mut error = 0;
+    for ticket in &puzzle.nearby {
+        for value in ticket {
+            if !valid.contains(value) {
+                error += value;
+            }
+        }
+    }
+    error
+}
+
+pub fn solve2(puzzle: &Puzzle) -> u64 {
+    let mut value_to_conditions: HashMap<u64, HashSet<String>> = HashMap::new();
+
+    for condition in &puzzle.conditions {
+        for range in &condition.ranges {
+            for i in range.from..=range.to {
+                value_to_conditions
+                    .entry(i)
+                    .or_default()
+                    .insert(condition.name.clone());
+            }
+        }
+    }
+
+    let mut valid = Vec::new();
+    for ticket in &puzzle.nearby {
+        if ticket
+            .iter()
+            .all(|value| value_to_conditions.contains_key(value))
+        {
+            valid.push(ticket.clone());
+        }
+    }
+
+    let mut departure_values = Vec::new();
+    let mut name_to_col = HashMap::new();
+
+    while name_to_col.len() != puzzle.conditions.len() {
+        for col in 0..puzzle.conditions.len() {
+            let mut possible: HashSet<String> = HashSet::new();
+
+            for (i, ticket) in valid.iter().enumerate() {
+                if let Some(conditions) = value_to_conditions.get(&ticket[col]) {
+                    if i == 0 {
+                        for cond in conditions {
+                            possible.insert(cond.clone());
+                        }
+                    } else {
+                        possible.retain(|v| conditions.contains(v));
+                    }
+                }
+            }
+
+            if possible.len() == 1 {
+                let col_name = possible.iter().next().unwrap();
+                name_to_col.insert(col_name.clone(), col);
+
+                for v in value_to_conditions.values_mut() {
+                    v.remove(col_name);
+                }
+
+                if col_name.starts_with("departure") {
+                    departure_values.push(puzzle.your[col]);
+                }
+            }
+        }
+    }
+
+    departure_values.iter().product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12
+";
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve(&puzzle), 71);
+    }
+}
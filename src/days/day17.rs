@@ -0,0 +1,103 @@
+//! https://adventofcode.com/2020/day/17
+
+use crate::automaton::{moore_offsets, Automaton, Rule};
+use crate::grid::Grid;
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        solve2(puzzle)
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    /// The `(x, y)` positions of the active cubes in the initial slice.
+    active: Vec<(i64, i64)>,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day17::DAY, input);
+    let slice = Grid::parse(&text, input, "`#` or `.`", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let active = slice
+        .iter()
+        .filter(|(_, &active)| active)
+        .map(|((x, y), _)| (x as i64, y as i64))
+        .collect();
+    Ok(Puzzle { active })
+}
+
+pub fn solve(puzzle: &Puzzle) -> usize {
+    simulate(puzzle, 3)
+}
+
+pub fn solve2(puzzle: &Puzzle) -> usize {
+    simulate(puzzle, 4)
+}
+
+/// Runs 6 cycles with the initial slice in a space of 3 or 4 dimensions, returns the number of
+/// active cubes.
+fn simulate(puzzle: &Puzzle, dimensions: usize) -> usize {
+    // Positions always have 4 coordinates, the ones for unused dimensions stay 0
+    let offsets: Vec<[i64; 4]> = moore_offsets(dimensions)
+        .iter()
+        .map(|offset| {
+            let mut o = [0; 4];
+            o[..dimensions].copy_from_slice(offset);
+            o
+        })
+        .collect();
+    let neighbours = |pos: &[i64; 4]| -> Vec<[i64; 4]> {
+        offsets
+            .iter()
+            .map(|o| [pos[0] + o[0], pos[1] + o[1], pos[2] + o[2], pos[3] + o[3]])
+            .collect()
+    };
+
+    let active = puzzle.active.iter().map(|&(x, y)| [x, y, 0, 0]).collect();
+    let automaton = Automaton::new(neighbours, Rule::life());
+    automaton.run(active, 6).live.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = ".#.
+..#
+###
+";
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve(&puzzle), 112);
+        assert_eq!(solve2(&puzzle), 848);
+    }
+
+    #[test]
+    fn test_empty() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a map");
+    }
+}
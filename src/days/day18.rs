@@ -0,0 +1,570 @@
+//! https://adventofcode.com/2020/day/18
+
+use crate::parse::{ParseError, Text};
+use crate::{Answer, Solution};
+use std::convert::TryFrom;
+use std::fmt;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Input = Homework;
+    type Answer1 = Answer<i64>;
+    type Answer2 = Answer<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(homework: &Self::Input) -> Self::Answer1 {
+        solve(homework)
+    }
+
+    fn part2(homework: &Self::Input) -> Self::Answer2 {
+        solve2(homework)
+    }
+}
+
+/// The expressions of the input, parsed with the operator table of each part.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Homework {
+    flat: Vec<Expr>,
+    addition_first: Vec<Expr>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+impl BinOp {
+    pub const ALL: [BinOp; 5] = [BinOp::Add, BinOp::Sub, BinOp::Mul, BinOp::Div, BinOp::Pow];
+
+    pub fn from_char(c: char) -> Option<BinOp> {
+        BinOp::ALL.iter().copied().find(|op| op.symbol() == c)
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+            BinOp::Pow => '^',
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expr {
+    Num(i64),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`
+    Right,
+}
+
+/// The precedence levels of the operators, which the parser and pretty-printer are driven by.
+///
+/// Built from the lowest level to the highest, in code:
+///
+/// ```
+/// # use advent_of_code_2020::days::day18::{BinOp, OperatorTable};
+/// let table = OperatorTable::new()
+///     .left(&[BinOp::Mul, BinOp::Div])
+///     .left(&[BinOp::Add, BinOp::Sub])
+///     .negation()
+///     .right(&[BinOp::Pow]);
+/// assert_eq!(table, OperatorTable::addition_first());
+/// ```
+///
+/// Or from a spec with one level per line, see [`OperatorTable::parse`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperatorTable {
+    /// The binary operators with their level, higher binds tighter.
+    binary: Vec<(BinOp, u8, Associativity)>,
+    /// The level of unary minus, if it's allowed.
+    negation: Option<u8>,
+    levels: u8,
+}
+
+impl OperatorTable {
+    /// A table without any operators, so only numbers and parentheses.
+    pub fn new() -> OperatorTable {
+        OperatorTable::default()
+    }
+
+    /// Part 1: `+`, `-`, `*` and `/` have the same precedence and are evaluated left to right.
+    /// Unary minus binds tighter, and `^` even more so.
+    pub fn flat() -> OperatorTable {
+        OperatorTable::new()
+            .left(&[BinOp::Add, BinOp::Sub, BinOp::Mul, BinOp::Div])
+            .negation()
+            .right(&[BinOp::Pow])
+    }
+
+    /// Part 2: Like [`OperatorTable::flat`], but `+` and `-` bind tighter than `*` and `/`.
+    pub fn addition_first() -> OperatorTable {
+        OperatorTable::new()
+            .left(&[BinOp::Mul, BinOp::Div])
+            .left(&[BinOp::Add, BinOp::Sub])
+            .negation()
+            .right(&[BinOp::Pow])
+    }
+
+    /// Adds a level of left associative operators above the existing ones.
+    pub fn left(self, ops: &[BinOp]) -> OperatorTable {
+        self.level(ops, Associativity::Left)
+    }
+
+    /// Adds a level of right associative operators above the existing ones.
+    pub fn right(self, ops: &[BinOp]) -> OperatorTable {
+        self.level(ops, Associativity::Right)
+    }
+
+    /// Adds a level for unary minus above the existing ones.
+    pub fn negation(mut self) -> OperatorTable {
+        self.levels += 1;
+        self.negation = Some(self.levels);
+        self
+    }
+
+    fn level(mut self, ops: &[BinOp], associativity: Associativity) -> OperatorTable {
+        self.levels += 1;
+        for &op in ops {
+            self.binary.retain(|&(o, _, _)| o != op);
+            self.binary.push((op, self.levels, associativity));
+        }
+        self
+    }
+
+    /// Parses a spec with one level per line, from the lowest to the highest. A level is
+    /// `left` or `right` followed by operators, or `prefix -` for unary minus, e.g. for part 2:
+    ///
+    /// ```text
+    /// left * /
+    /// left + -
+    /// prefix -
+    /// right ^
+    /// ```
+    pub fn parse(spec: &str) -> Result<OperatorTable, String> {
+        let mut table = OperatorTable::new();
+        for line in spec.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let mut words = line.split_whitespace();
+            let kind = words.next().unwrap();
+            let symbols: Vec<&str> = words.collect();
+            table = match kind {
+                "prefix" if symbols == ["-"] => table.negation(),
+                "prefix" => return Err(format!("Only `prefix -` is supported: {:?}", line)),
+                "left" | "right" => {
+                    let ops = symbols
+                        .iter()
+                        .map(|s| {
+                            let mut chars = s.chars();
+                            match (chars.next().and_then(BinOp::from_char), chars.next()) {
+                                (Some(op), None) => Ok(op),
+                                _ => Err(format!("Unknown operator {:?} in {:?}", s, line)),
+                            }
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if kind == "left" {
+                        table.left(&ops)
+                    } else {
+                        table.right(&ops)
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Expected `left`, `right` or `prefix` at the start of {:?}",
+                        line
+                    ))
+                }
+            };
+        }
+        Ok(table)
+    }
+
+    fn get(&self, op: BinOp) -> Option<(u8, Associativity)> {
+        self.binary
+            .iter()
+            .find(|&&(o, _, _)| o == op)
+            .map(|&(_, level, associativity)| (level, associativity))
+    }
+
+    fn level_of(&self, op: BinOp) -> (u8, Associativity) {
+        self.get(op)
+            .unwrap_or_else(|| panic!("Operator {} is not in the table", op.symbol()))
+    }
+
+    fn negation_level(&self) -> u8 {
+        self.negation.expect("Unary minus is not in the table")
+    }
+}
+
+impl Expr {
+    fn binary(op: BinOp, left: Expr, right: Expr) -> Expr {
+        Expr::Binary(op, Box::new(left), Box::new(right))
+    }
+
+    /// Parses a whole expression, `None` if it's not valid.
+    pub fn parse(s: &str, table: &OperatorTable) -> Option<Expr> {
+        parse_expr(s, table).ok()
+    }
+
+    /// Combines the values of the subexpressions bottom up, e.g. to evaluate the expression.
+    pub fn fold<T>(
+        &self,
+        num: &impl Fn(i64) -> T,
+        neg: &impl Fn(T) -> T,
+        binary: &impl Fn(BinOp, T, T) -> T,
+    ) -> T {
+        match self {
+            Expr::Num(n) => num(*n),
+            Expr::Neg(e) => neg(e.fold(num, neg, binary)),
+            Expr::Binary(op, left, right) => binary(
+                *op,
+                left.fold(num, neg, binary),
+                right.fold(num, neg, binary),
+            ),
+        }
+    }
+
+    /// Evaluates the expression, `None` on division by zero, a negative exponent or overflow.
+    /// Division rounds towards zero.
+    pub fn eval(&self) -> Option<i64> {
+        self.fold(&Some, &|e: Option<i64>| e?.checked_neg(), &|op, l, r| {
+            let (l, r) = (l?, r?);
+            match op {
+                BinOp::Add => l.checked_add(r),
+                BinOp::Sub => l.checked_sub(r),
+                BinOp::Mul => l.checked_mul(r),
+                BinOp::Div => l.checked_div(r),
+                BinOp::Pow => l.checked_pow(u32::try_from(r).ok()?),
+            }
+        })
+    }
+
+    /// Formats the expression with only the parentheses that are needed to parse it back to the
+    /// same tree with the table. Panics if it uses operators that are not in the table.
+    pub fn pretty<'a>(&'a self, table: &'a OperatorTable) -> Pretty<'a> {
+        Pretty { expr: self, table }
+    }
+
+    fn binding(&self, table: &OperatorTable) -> u8 {
+        match self {
+            Expr::Num(n) if *n < 0 => table.negation_level(),
+            Expr::Num(_) => u8::MAX,
+            Expr::Neg(_) => table.negation_level(),
+            Expr::Binary(op, _, _) => table.level_of(*op).0,
+        }
+    }
+}
+
+/// See [`Expr::pretty`].
+pub struct Pretty<'a> {
+    expr: &'a Expr,
+    table: &'a OperatorTable,
+}
+
+impl Pretty<'_> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, expr: &Expr, parens: bool) -> fmt::Result {
+        if parens {
+            write!(f, "(")?;
+        }
+        match expr {
+            Expr::Num(n) => write!(f, "{}", n)?,
+            Expr::Neg(e) => {
+                write!(f, "-")?;
+                self.write(f, e, e.binding(self.table) < self.table.negation_level())?;
+            }
+            Expr::Binary(op, left, right) => {
+                let (p, associativity) = self.table.level_of(*op);
+                let (l, r) = (left.binding(self.table), right.binding(self.table));
+                let left_parens = l < p || (l == p && associativity == Associativity::Right);
+                // This includes a negation on the right that binds less tightly than the operator,
+                // which would otherwise also take the operators after it, e.g. in `1 + -2 + 3`
+                let right_parens = r < p || (r == p && associativity == Associativity::Left);
+                self.write(f, left, left_parens)?;
+                write!(f, " {} ", op.symbol())?;
+                self.write(f, right, right_parens)?;
+            }
+        }
+        if parens {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Pretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, self.expr, false)
+    }
+}
+
+/// Parses each line with the tables of both parts.
+pub fn parse(input: &str) -> Result<Homework, ParseError> {
+    let text = Text::new(Day18::DAY, input);
+    let parse_all = |table: &OperatorTable| {
+        text.lines()
+            .map(|line| {
+                parse_expr(line, table).map_err(|(rest, expected)| text.error(rest, &expected))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    Ok(Homework {
+        flat: parse_all(&OperatorTable::flat())?,
+        addition_first: parse_all(&OperatorTable::addition_first())?,
+    })
+}
+
+pub fn solve(homework: &Homework) -> Answer<i64> {
+    sum(&homework.flat)
+}
+
+pub fn solve2(homework: &Homework) -> Answer<i64> {
+    sum(&homework.addition_first)
+}
+
+/// The sum of the values of all lines, no answer if a line or the sum can't be computed.
+fn sum(exprs: &[Expr]) -> Answer<i64> {
+    let mut sum: i64 = 0;
+    for (i, expr) in exprs.iter().enumerate() {
+        let value = match expr.eval() {
+            Some(value) => value,
+            None => {
+                return Answer::Unsolved(format!(
+                    "line {} has a division by zero, a negative exponent or overflows",
+                    i + 1
+                ))
+            }
+        };
+        sum = match sum.checked_add(value) {
+            Some(sum) => sum,
+            None => return Answer::Unsolved(format!("the sum overflows at line {}", i + 1)),
+        };
+    }
+    Answer::Solved(sum)
+}
+
+/// The rest of the input where parsing failed, and what was expected there.
+type Failure<'a> = (&'a str, String);
+
+fn parse_expr<'a>(s: &'a str, table: &OperatorTable) -> Result<Expr, Failure<'a>> {
+    let parser = Parser { table };
+    let (expr, rest) = parser.expr(s, 0)?;
+    if rest.is_empty() {
+        Ok(expr)
+    } else {
+        Err((rest, "an operator and operand, or end of line".to_string()))
+    }
+}
+
+/// A precedence climbing parser, see
+/// https://eli.thegreenplace.net/2012/08/02/parsing-expressions-by-precedence-climbing
+struct Parser<'t> {
+    table: &'t OperatorTable,
+}
+
+impl Parser<'_> {
+    /// Parses an expression with binary operators of at least `min_level`, returns it and the
+    /// rest of the input after any spaces.
+    fn expr<'a>(&self, i: &'a str, min_level: u8) -> Result<(Expr, &'a str), Failure<'a>> {
+        let (mut left, mut i) = self.unary(i)?;
+        while let Some(op) = i.chars().next().and_then(BinOp::from_char) {
+            let (level, associativity) = match self.table.get(op) {
+                Some((level, associativity)) if level >= min_level => (level, associativity),
+                _ => break,
+            };
+            let next_level = match associativity {
+                Associativity::Left => level + 1,
+                Associativity::Right => level,
+            };
+            let (right, rest) = self.expr(&i[1..], next_level)?;
+            left = Expr::binary(op, left, right);
+            i = rest;
+        }
+        Ok((left, i))
+    }
+
+    /// A negation or an operand.
+    fn unary<'a>(&self, i: &'a str) -> Result<(Expr, &'a str), Failure<'a>> {
+        let i = i.trim_start();
+        match (i.strip_prefix('-'), self.table.negation) {
+            (Some(rest), Some(level)) => {
+                let (e, rest) = self.expr(rest, level)?;
+                Ok((Expr::Neg(Box::new(e)), rest))
+            }
+            _ => self.operand(i),
+        }
+    }
+
+    fn operand<'a>(&self, i: &'a str) -> Result<(Expr, &'a str), Failure<'a>> {
+        if let Some(rest) = i.strip_prefix('(') {
+            let (e, rest) = self.expr(rest, 0)?;
+            return match rest.strip_prefix(')') {
+                Some(rest) => Ok((e, rest.trim_start())),
+                None => Err((rest, "an operator or `)`".to_string())),
+            };
+        }
+
+        let digits = i.find(|c: char| !c.is_ascii_digit()).unwrap_or(i.len());
+        match i[..digits].parse() {
+            Ok(n) => Ok((Expr::Num(n), i[digits..].trim_start())),
+            Err(_) if self.table.negation.is_some() => Err((i, "a number, `-` or `(`".to_string())),
+            Err(_) => Err((i, "a number or `(`".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("1 + 2\n2 * (3 + 4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.expected, "an operator or `)`");
+
+        let error = parse("1 + 2 3").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.expected, "an operator and operand, or end of line");
+
+        let error = parse("x + 1").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a number, `-` or `(`");
+    }
+
+    /// The answers of both parts, which have to be solved.
+    fn answers(input: &str) -> (i64, i64) {
+        let homework = parse(input).unwrap();
+        match (solve(&homework), solve2(&homework)) {
+            (Answer::Solved(one), Answer::Solved(two)) => (one, two),
+            answers => panic!("Expected answers, got {:?}", answers),
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(answers("1 + (2 * 3) + (4 * (5 + 6))"), (51, 51));
+        assert_eq!(answers("2 * 3 + (4 * 5)"), (26, 46));
+        assert_eq!(answers("5 + (8 * 3 + 9 + 3 * 4 * 3)"), (437, 1445));
+        assert_eq!(
+            answers("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"),
+            (12240, 669060)
+        );
+        assert_eq!(
+            answers("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+            (13632, 23340)
+        );
+    }
+
+    #[test]
+    fn test_more_operators() {
+        assert_eq!(answers("10 - 2 * 3"), (24, 24));
+        assert_eq!(answers("2 * 3 - 10 / 2"), (-2, -7));
+        assert_eq!(answers("2 ^ 3 ^ 2 + 1").0, 513);
+        assert_eq!(answers("-2 ^ 2 * -(3 - 5)").0, -8);
+        assert_eq!(answers("123 + -4").1, 119);
+        assert_eq!(answers("1 + 2 * 3\n4 * 5 - 1"), (9 + 19, 9 + 16));
+        assert_eq!(
+            Expr::parse("1 / (2 - 2)", &OperatorTable::flat())
+                .unwrap()
+                .eval(),
+            None
+        );
+        assert_eq!(
+            Expr::parse("2 ^ -1", &OperatorTable::flat())
+                .unwrap()
+                .eval(),
+            None
+        );
+        assert_eq!(Expr::parse("2 + ", &OperatorTable::flat()), None);
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        let homework = parse("1 + 2\n3 * 4 / (2 - 2)").unwrap();
+        assert_eq!(
+            solve(&homework).to_string(),
+            "no answer: line 2 has a division by zero, a negative exponent or overflows"
+        );
+
+        // Overflows only when adding first
+        let homework = parse("4000000000 * 0 + 4000000000").unwrap();
+        assert_eq!(solve(&homework), Answer::Solved(4000000000));
+        assert!(matches!(solve2(&homework), Answer::Unsolved(_)));
+
+        // Each line fits, but their sum doesn't
+        let homework = parse("9223372036854775807\n1").unwrap();
+        assert_eq!(
+            solve(&homework),
+            Answer::Unsolved("the sum overflows at line 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pretty() {
+        let (flat, addition_first) = (OperatorTable::flat(), OperatorTable::addition_first());
+        let pretty = |s: &str, table: &OperatorTable| {
+            let expr = Expr::parse(s, table).unwrap();
+            let pretty = expr.pretty(table).to_string();
+            assert_eq!(Expr::parse(&pretty, table), Some(expr));
+            pretty
+        };
+        assert_eq!(pretty("((1 + 2)) * (3)", &flat), "1 + 2 * 3");
+        assert_eq!(pretty("(1 + 2) * 3", &addition_first), "1 + 2 * 3");
+        assert_eq!(pretty("1 + (2 * 3)", &addition_first), "1 + (2 * 3)");
+        assert_eq!(pretty("1 * (2 + 3)", &flat), "1 * (2 + 3)");
+        assert_eq!(pretty("1 * (2 + 3)", &addition_first), "1 * 2 + 3");
+        assert_eq!(pretty("1 - (2 - 3)", &flat), "1 - (2 - 3)");
+        assert_eq!(pretty("(2 ^ 3) ^ 2", &flat), "(2 ^ 3) ^ 2");
+        assert_eq!(pretty("2 ^ (3 ^ 2)", &flat), "2 ^ 3 ^ 2");
+        assert_eq!(pretty("(-2) ^ 2", &flat), "(-2) ^ 2");
+        assert_eq!(pretty("-(2 ^ 2)", &flat), "-2 ^ 2");
+        assert_eq!(pretty("-(1 + 2) * (-3)", &flat), "-(1 + 2) * -3");
+        assert_eq!(pretty("2 ^ (-1)", &flat), "2 ^ (-1)");
+
+        // Unary minus binding less tightly than `+`
+        let table = OperatorTable::parse("prefix -\nleft +").unwrap();
+        assert_eq!(pretty("1 + (-2) + 3", &table), "1 + (-2) + 3");
+        assert_eq!(pretty("1 + -(2 + 3)", &table), "1 + (-2 + 3)");
+        assert_eq!(pretty("-1 + 2", &table), "-1 + 2");
+        assert_eq!(pretty("(-1) + 2", &table), "(-1) + 2");
+    }
+
+    #[test]
+    fn test_table_spec() {
+        let spec = "left * /\nleft + -\nprefix -\nright ^\n";
+        assert_eq!(
+            OperatorTable::parse(spec),
+            Ok(OperatorTable::addition_first())
+        );
+        assert!(OperatorTable::parse("left + %").is_err());
+        assert!(OperatorTable::parse("middle +").is_err());
+        assert!(OperatorTable::parse("prefix +").is_err());
+
+        // Alien math: subtraction before addition, and everything right associative
+        let table = OperatorTable::parse("right +\nright -").unwrap();
+        let expr = Expr::parse("10 - 2 + 3 - 1", &table).unwrap();
+        assert_eq!(expr.eval(), Some(8 + 2));
+        assert_eq!(expr.pretty(&table).to_string(), "10 - 2 + 3 - 1");
+        assert_eq!(Expr::parse("-1", &table), None);
+        assert_eq!(
+            parse_expr("1 * 2", &table).unwrap_err(),
+            ("* 2", "an operator and operand, or end of line".to_string())
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! https://adventofcode.com/2020/day/19
+
+use crate::grammar::{Matcher, Rule, Rules};
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use std::collections::HashMap;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        solve2(puzzle)
+    }
+}
+
+#[derive(Clone)]
+pub struct Puzzle {
+    rules: Rules,
+    messages: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day19::DAY, input);
+    let (puzzle, references) = parse_with_references(&text)?;
+
+    for n in references {
+        if !puzzle.rules.contains_key(&n.parse().unwrap()) {
+            return Err(text.error(n, "a rule number that is defined"));
+        }
+    }
+    if !puzzle.rules.contains_key(&0) {
+        return Err(text.error_at_end("a rule 0"));
+    }
+
+    Ok(puzzle)
+}
+
+/// Parses the rules and messages without checking that the referenced rules and rule 0 exist,
+/// e.g. for analysing a grammar that is being edited, see [`crate::grammar::Analysis`].
+pub fn parse_unchecked(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(parse_with_references(&Text::new(Day19::DAY, input))?.0)
+}
+
+/// Also returns the referenced rule numbers, to check that they exist.
+fn parse_with_references<'a>(text: &Text<'a>) -> Result<(Puzzle, Vec<&'a str>), ParseError> {
+    let mut rules = HashMap::new();
+    let mut messages = Vec::new();
+    let mut parse_messages = false;
+    let mut references = Vec::new();
+
+    for line in text.lines() {
+        if line.is_empty() {
+            parse_messages = true;
+            continue;
+        }
+
+        if parse_messages {
+            messages.push(line.to_string());
+            continue;
+        }
+
+        let (num, desc) = text.split_once(line, ": ")?;
+        let num = text.parse::<usize>(num, "a rule number")?;
+
+        if let Some(c) = desc.strip_prefix('"') {
+            let c = text.strip_suffix(c, "\"")?;
+            rules.insert(num, Rule::Char(text.parse(c, "a single character")?));
+        } else {
+            let mut alts = Vec::new();
+            for alt in desc.split(" | ") {
+                let mut list = Vec::new();
+                for n in alt.split_whitespace() {
+                    list.push(text.parse::<usize>(n, "a rule number")?);
+                    references.push(n);
+                }
+                if list.is_empty() {
+                    return Err(text.error(alt, "a list of rule numbers"));
+                }
+                alts.push(Rule::List(list));
+            }
+            if alts.len() == 1 {
+                rules.insert(num, alts.pop().unwrap());
+            } else {
+                rules.insert(num, Rule::Alt(alts));
+            }
+        }
+    }
+
+    Ok((Puzzle { rules, messages }, references))
+}
+
+pub fn solve(puzzle: &Puzzle) -> usize {
+    count_matches(&puzzle.rules, &puzzle.messages)
+}
+
+pub fn solve2(puzzle: &Puzzle) -> usize {
+    // Modified rules, which make the grammar recursive:
+    // 8: 42 | 42 8
+    // 11: 42 31 | 42 11 31
+    let mut rules = puzzle.rules.clone();
+    rules.insert(
+        8,
+        Rule::Alt(vec![Rule::List(vec![42]), Rule::List(vec![42, 8])]),
+    );
+    rules.insert(
+        11,
+        Rule::Alt(vec![Rule::List(vec![42, 31]), Rule::List(vec![42, 11, 31])]),
+    );
+    count_matches(&rules, &puzzle.messages)
+}
+
+fn count_matches(rules: &Rules, messages: &[String]) -> usize {
+    let matcher = Matcher::new(rules);
+    messages.iter().filter(|m| matcher.matches(0, m)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_part1() {
+        let input = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#;
+
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve(&puzzle), 2);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        let input = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve2(&puzzle), 12);
+    }
+
+    #[test]
+    fn test_recursive() {
+        // Palindromes of any length, and left recursion
+        let input = r#"0: 1 0 1 | 2 0 2 | 1 | 2 | 1 1 | 2 2
+1: "a"
+2: "b"
+3: 3 1 | 1
+
+abba
+ababa
+abbab"#;
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve(&puzzle), 2);
+
+        let matcher = Matcher::new(&puzzle.rules);
+        let long = "ab".repeat(50) + &"ba".repeat(50);
+        assert!(matcher.matches(0, &long));
+        assert!(!matcher.matches(0, &(long + "a")));
+        assert!(matcher.matches(3, &"a".repeat(200)));
+        assert!(!matcher.matches(3, ""));
+        assert!(!matcher.matches(3, "aab"));
+    }
+}
@@ -0,0 +1,1254 @@
+//! https://adventofcode.com/2020/day/20
+
+use crate::bitgrid::BitGrid;
+use crate::grid::{Grid, Pos};
+use crate::parse::{ParseError, Text};
+use crate::{Answer, Solution};
+use itertools::Itertools;
+use num::integer::sqrt;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+/// The sides of tiles are stored as bits of a `u64`.
+const MAX_TILE_LENGTH: usize = 64;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input = Puzzle;
+    type Answer1 = Answer<u64>;
+    type Answer2 = Answer<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        match assemble(puzzle) {
+            Some(arrangement) => Answer::Solved(arrangement.corner_product()),
+            None => no_arrangement(),
+        }
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        match solve(puzzle) {
+            Some((_, image)) => Answer::Solved(solve2(&image)),
+            None => no_arrangement(),
+        }
+    }
+}
+
+fn no_arrangement<T>() -> Answer<T> {
+    Answer::Unsolved("the tiles can't be arranged in a rectangle".to_string())
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day20::DAY, input);
+    let mut tiles = HashMap::new();
+    // The size of the first tile, which all others need to have too
+    let mut tile_length = None;
+
+    // Blank lines at the end would give empty tiles
+    for tile_str in input.split("\n\n").filter(|s| !s.trim().is_empty()) {
+        let (header, rest) = text.split_once(tile_str, "\n")?;
+
+        let id = text.strip_prefix(header, "Tile ")?;
+        let id = text.strip_suffix(id, ":")?;
+        let id = text.parse::<u64>(id, "a tile ID")?;
+
+        let content = Grid::parse(&text, rest, "`#` or `.`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        let length = content.width();
+        if content.height() != length {
+            return Err(text.error(rest, "a square tile"));
+        }
+        match tile_length {
+            None if length < 3 => return Err(text.error(rest, "a tile of at least 3x3 pixels")),
+            None if length > MAX_TILE_LENGTH => {
+                return Err(text.error(rest, "a tile of at most 64x64 pixels"))
+            }
+            None => tile_length = Some(length),
+            Some(expected) if expected != length => {
+                return Err(text.error(
+                    rest,
+                    &format!("a tile of {0}x{0} pixels like the first one", expected),
+                ))
+            }
+            Some(_) => {}
+        }
+
+        tiles.insert(id, Tile::new(id, BitGrid::from(&content)));
+    }
+
+    if tiles.is_empty() {
+        return Err(text.error_at_end("a tile"));
+    }
+    Ok(Puzzle { tiles })
+}
+
+/// Returns the product of the IDs of the corner tiles (each counted once, in case the tiles are
+/// in a single row), and the image without the borders of the tiles. `None` if the tiles can't
+/// be arranged in a rectangle.
+pub fn solve(puzzle: &Puzzle) -> Option<(u64, BitGrid)> {
+    let arrangement = assemble(puzzle)?;
+    Some((arrangement.corner_product(), arrangement.image(puzzle)))
+}
+
+/// Where the tiles go and how they're oriented.
+pub struct Arrangement {
+    /// In tiles.
+    pub width: usize,
+    pub height: usize,
+    tiles: HashMap<Pos, ArrangedTile>,
+}
+
+/// Arranges the tiles so that the sides of neighbours match, `None` if that's not possible.
+pub fn assemble(puzzle: &Puzzle) -> Option<Arrangement> {
+    assemble_all(puzzle, 1).arrangements.pop()
+}
+
+/// The arrangements found by [`assemble_all`].
+pub struct Assembly {
+    /// Distinct up to rotating and flipping the whole image, at most as many as the limit.
+    pub arrangements: Vec<Arrangement>,
+    /// Whether the search finished before reaching the limit, so that there are no others.
+    pub complete: bool,
+}
+
+/// Finds up to `limit` arrangements where the sides of all neighbours match, which can be more
+/// than one if some sides are shared by several tiles. Arrangements that are rotations or flips
+/// of the whole image of one that was already found don't count.
+///
+/// The tiles can be arranged in any rectangle, not just a square. The layouts closest to a
+/// square are tried first, and only wider than high ones, as rotating the whole image covers the
+/// others.
+pub fn assemble_all(puzzle: &Puzzle, limit: usize) -> Assembly {
+    let count = puzzle.tiles.len();
+    let index = side_index(&puzzle.tiles);
+    let mut arrangements = Vec::new();
+    for height in (1..=sqrt(count)).rev().filter(|&h| count.is_multiple_of(h)) {
+        if arrangements.len() >= limit {
+            break;
+        }
+        let mut search = Search::new(&puzzle.tiles, &index, count / height, height);
+        search.limit = limit - arrangements.len();
+        search.run();
+        arrangements.extend(search.found);
+    }
+    Assembly {
+        complete: arrangements.len() < limit,
+        arrangements,
+    }
+}
+
+impl Arrangement {
+    /// The arrangement after rotating and/or flipping the whole image.
+    pub fn transform(&self, orientation: Orientation) -> Arrangement {
+        let size = (self.width, self.height);
+        let (width, height) = orientation.apply_size(size);
+        let tiles = self
+            .tiles
+            .iter()
+            .map(|(&pos, tile)| {
+                let moved = ArrangedTile {
+                    id: tile.id,
+                    orientation: tile.orientation.then(orientation),
+                };
+                (orientation.apply_pos(pos, size), moved)
+            })
+            .collect();
+        Arrangement {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    /// The same for all arrangements that are symmetries of each other with the same size: the
+    /// smallest list of tiles (ID, rotation and flip) in row order.
+    fn canonical(&self) -> Vec<(u64, u8, bool)> {
+        Orientation::all()
+            .filter(|o| o.apply_size((self.width, self.height)) == (self.width, self.height))
+            .map(|o| {
+                let transformed = self.transform(o);
+                (0..self.height)
+                    .cartesian_product(0..self.width)
+                    .map(|(y, x)| {
+                        let tile = transformed.tiles[&(x, y)];
+                        (
+                            tile.id,
+                            tile.orientation.rotation(),
+                            tile.orientation.flip(),
+                        )
+                    })
+                    .collect()
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// The tile at a position, in tiles.
+    pub fn get(&self, pos: Pos) -> Option<&ArrangedTile> {
+        self.tiles.get(&pos)
+    }
+
+    pub fn corner_product(&self) -> u64 {
+        let (right, bottom) = (self.width - 1, self.height - 1);
+        let corners: HashSet<Pos> = vec![(0, 0), (right, 0), (0, bottom), (right, bottom)]
+            .into_iter()
+            .collect();
+        corners.iter().map(|pos| self.tiles[pos].id).product()
+    }
+
+    /// The tiles put together, without their borders.
+    pub fn image(&self, puzzle: &Puzzle) -> BitGrid {
+        let inner = puzzle.tiles[&self.tiles[&(0, 0)].id].content.width() - 2;
+        let mut image = BitGrid::new(self.width * inner, self.height * inner);
+        for (&(x, y), &ArrangedTile { id, orientation }) in &self.tiles {
+            let content = puzzle.tiles[&id].content(orientation);
+            for row in 0..inner {
+                let bits = content.row_bits(1, row + 1, inner);
+                image.set_row_bits(x * inner, y * inner + row, bits, inner);
+            }
+        }
+        image
+    }
+
+    /// A line per tile with its position, ID, orientation and its sides as they are compared
+    /// to the neighbours (read from left to right or top to bottom), for inspecting and diffing
+    /// arrangements.
+    pub fn map(&self, puzzle: &Puzzle) -> String {
+        let mut map = String::from("# x y id rotation flip top right bottom left\n");
+        for (y, x) in (0..self.height).cartesian_product(0..self.width) {
+            let ArrangedTile { id, orientation } = self.tiles[&(x, y)];
+            let tile = &puzzle.tiles[&id];
+            let sides = (0..4).map(|side| tile.side(side, orientation)).join(" ");
+            map.push_str(&format!(
+                "{} {} {} {} {} {}\n",
+                x,
+                y,
+                id,
+                orientation.rotation(),
+                orientation.flip(),
+                sides
+            ));
+        }
+        map
+    }
+}
+
+/// The tiles and orientations by side (0 top, 1 right, 2 bottom, 3 left) and its value, to look
+/// up the ones that fit next to a placed tile.
+type SideIndex = HashMap<(usize, u64), Vec<ArrangedTile>>;
+
+fn side_index(tiles: &HashMap<u64, Tile>) -> SideIndex {
+    let mut index = SideIndex::new();
+    for tile in tiles.values() {
+        for orientation in Orientation::all() {
+            for side in 0..4 {
+                index
+                    .entry((side, tile.side(side, orientation)))
+                    .or_default()
+                    .push(ArrangedTile {
+                        id: tile.id,
+                        orientation,
+                    });
+            }
+        }
+    }
+    index
+}
+
+/// Backtracking search for the arrangements in a rectangle of a given size, with constraint
+/// propagation: every cell next to a placed tile keeps the tiles and orientations that still fit
+/// all its placed neighbours (its domain). Placing a tile narrows down the domains of its
+/// neighbours and removes it from all others, and fails as soon as a domain is empty. The next
+/// cell to fill is the one with the smallest domain.
+///
+/// The search starts by putting the tile with the lowest ID in each cell. Its orientation can be
+/// fixed, as rotating or flipping the whole image turns it into any other one, so symmetric
+/// copies of an arrangement are mostly not even searched.
+struct Search<'a> {
+    tiles: &'a HashMap<u64, Tile>,
+    index: &'a SideIndex,
+    width: usize,
+    height: usize,
+    /// Row by row.
+    placed: Vec<Option<ArrangedTile>>,
+    domains: Vec<Option<Vec<ArrangedTile>>>,
+    /// The empty cells that have a domain.
+    frontier: BTreeSet<usize>,
+    used: HashSet<u64>,
+    limit: usize,
+    /// The canonical forms of the arrangements found so far, see [`Search::canonical`].
+    seen: HashSet<Vec<(u64, u8, bool)>>,
+    found: Vec<Arrangement>,
+}
+
+/// The domains before a placement, for undoing it.
+type Undo = Vec<(usize, Option<Vec<ArrangedTile>>)>;
+
+impl<'a> Search<'a> {
+    fn new(
+        tiles: &'a HashMap<u64, Tile>,
+        index: &'a SideIndex,
+        width: usize,
+        height: usize,
+    ) -> Search<'a> {
+        Search {
+            tiles,
+            index,
+            width,
+            height,
+            placed: vec![None; width * height],
+            domains: vec![None; width * height],
+            frontier: BTreeSet::new(),
+            used: HashSet::new(),
+            limit: usize::MAX,
+            seen: HashSet::new(),
+            found: Vec::new(),
+        }
+    }
+
+    /// Returns whether the limit was reached.
+    fn run(&mut self) -> bool {
+        if self.found.len() >= self.limit {
+            return true;
+        }
+        let choices: Vec<(usize, ArrangedTile)> = match self.next_cell() {
+            Some(cell) => {
+                let domain = self.domains[cell].as_ref().unwrap();
+                domain.iter().map(|&option| (cell, option)).collect()
+            }
+            None if self.used.is_empty() => self.first_choices(),
+            None => {
+                self.record();
+                return self.found.len() >= self.limit;
+            }
+        };
+        for (cell, option) in choices {
+            if let Some(undo) = self.place(cell, option) {
+                if self.run() {
+                    return true;
+                }
+                self.unplace(cell, option, undo);
+            }
+        }
+        false
+    }
+
+    /// The cell with the fewest options, `None` if no tile is placed yet or all of them are (as
+    /// then all empty cells have a placed neighbour).
+    fn next_cell(&self) -> Option<usize> {
+        self.frontier
+            .iter()
+            .min_by_key(|&&cell| self.domains[cell].as_ref().unwrap().len())
+            .copied()
+    }
+
+    /// The tile with the lowest ID in every cell. A square image can be transformed so that the
+    /// tile isn't rotated or flipped, but for other rectangles only half of the symmetries keep
+    /// the size, which leaves the tile either rotated or not.
+    fn first_choices(&self) -> Vec<(usize, ArrangedTile)> {
+        let id = *self.tiles.keys().min().unwrap();
+        let mut orientations = vec![Orientation::IDENTITY];
+        if self.width != self.height {
+            orientations.push(Orientation::new(1, false));
+        }
+        (0..self.placed.len())
+            .cartesian_product(orientations)
+            .map(|(cell, orientation)| (cell, ArrangedTile { id, orientation }))
+            .collect()
+    }
+
+    /// Places the tile and propagates the constraints, `None` if that leaves a cell without
+    /// options (in which case nothing changed).
+    fn place(&mut self, cell: usize, tile: ArrangedTile) -> Option<Undo> {
+        self.placed[cell] = Some(tile);
+        self.used.insert(tile.id);
+        self.frontier.remove(&cell);
+        let mut undo = Undo::new();
+
+        let (x, y) = (cell % self.width, cell / self.width);
+        let mut neighbours = Vec::new();
+        if y > 0 {
+            neighbours.push((cell - self.width, 0, 2));
+        }
+        if x + 1 < self.width {
+            neighbours.push((cell + 1, 1, 3));
+        }
+        if y + 1 < self.height {
+            neighbours.push((cell + self.width, 2, 0));
+        }
+        if x > 0 {
+            neighbours.push((cell - 1, 3, 1));
+        }
+        let mut ok = true;
+        for (neighbour, side, opposite) in neighbours {
+            if self.placed[neighbour].is_some() {
+                continue;
+            }
+            let value = self.tiles[&tile.id].side(side, tile.orientation);
+            let domain: Vec<ArrangedTile> = match &self.domains[neighbour] {
+                Some(domain) => domain
+                    .iter()
+                    .filter(|o| self.tiles[&o.id].side(opposite, o.orientation) == value)
+                    .copied()
+                    .collect(),
+                None => self
+                    .index
+                    .get(&(opposite, value))
+                    .into_iter()
+                    .flatten()
+                    .filter(|o| !self.used.contains(&o.id))
+                    .copied()
+                    .collect(),
+            };
+            ok &= !domain.is_empty();
+            undo.push((neighbour, self.domains[neighbour].replace(domain)));
+            self.frontier.insert(neighbour);
+            if !ok {
+                break;
+            }
+        }
+
+        // The tile can't go anywhere else
+        if ok {
+            let others: Vec<usize> = self.frontier.iter().copied().collect();
+            for other in others {
+                let domain = self.domains[other].as_ref().unwrap();
+                if domain.iter().any(|o| o.id == tile.id) {
+                    let narrowed: Vec<ArrangedTile> =
+                        domain.iter().filter(|o| o.id != tile.id).copied().collect();
+                    ok &= !narrowed.is_empty();
+                    undo.push((other, self.domains[other].replace(narrowed)));
+                    if !ok {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if ok {
+            Some(undo)
+        } else {
+            self.unplace(cell, tile, undo);
+            None
+        }
+    }
+
+    fn unplace(&mut self, cell: usize, tile: ArrangedTile, undo: Undo) {
+        for (other, domain) in undo.into_iter().rev() {
+            if domain.is_none() {
+                self.frontier.remove(&other);
+            }
+            self.domains[other] = domain;
+        }
+        self.placed[cell] = None;
+        self.used.remove(&tile.id);
+        if self.domains[cell].is_some() {
+            self.frontier.insert(cell);
+        }
+    }
+
+    /// Adds the completely filled grid as an arrangement, unless it's a symmetry of one that was
+    /// already found.
+    fn record(&mut self) {
+        let tiles: HashMap<Pos, ArrangedTile> = self
+            .placed
+            .iter()
+            .enumerate()
+            .map(|(cell, tile)| ((cell % self.width, cell / self.width), tile.unwrap()))
+            .collect();
+        let arrangement = Arrangement {
+            width: self.width,
+            height: self.height,
+            tiles,
+        };
+        if self.seen.insert(arrangement.canonical()) {
+            self.found.push(arrangement);
+        }
+    }
+}
+
+/// The number of `#` that are not part of a sea monster.
+pub fn solve2(image: &BitGrid) -> usize {
+    let monster = Pattern::parse(SEA_MONSTER);
+    let matches = find_pattern(image, &monster);
+    image.count_ones() - covered(image, &matches).count_ones()
+}
+
+const SEA_MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+/// A shape to search for in an image, parsed from ASCII art where `#` are the pixels that need
+/// to be set and other characters can be anything.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    grid: BitGrid,
+    /// The positions of the `#`.
+    cells: Vec<(usize, usize)>,
+}
+
+impl Pattern {
+    pub fn parse(art: &str) -> Pattern {
+        let lines: Vec<Vec<char>> = art.lines().map(|l| l.chars().collect()).collect();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let grid = BitGrid::from_fn(width, lines.len(), |(x, y)| lines[y].get(x) == Some(&'#'));
+        Pattern::from_grid(grid)
+    }
+
+    fn from_grid(grid: BitGrid) -> Pattern {
+        let cells = grid.ones().collect();
+        Pattern { grid, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// The number of `#`.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn transform(&self, orientation: Orientation) -> Pattern {
+        Pattern::from_grid(orientation.apply_bits(&self.grid))
+    }
+}
+
+/// Where a pattern is in an image.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatternMatch {
+    /// The top left corner of the transformed pattern in the image.
+    pub x: usize,
+    pub y: usize,
+    /// How the pattern is rotated and flipped.
+    pub orientation: Orientation,
+    /// The pixels of the image that the pattern covers.
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// All the places where the pattern is in the image, in any of the 8 orientations. Matches can
+/// overlap. For symmetric patterns, orientations that result in the same shape are only
+/// reported once.
+pub fn find_pattern(image: &BitGrid, pattern: &Pattern) -> Vec<PatternMatch> {
+    let mut shapes: Vec<Pattern> = Vec::new();
+    let mut matches = Vec::new();
+    for orientation in Orientation::all() {
+        let shape = pattern.transform(orientation);
+        if shapes.contains(&shape) {
+            continue;
+        }
+        for (x, y) in image.find(&shape.grid) {
+            matches.push(PatternMatch {
+                x,
+                y,
+                orientation,
+                cells: shape
+                    .cells
+                    .iter()
+                    .map(|&(px, py)| (x + px, y + py))
+                    .collect(),
+            });
+        }
+        shapes.push(shape);
+    }
+    matches
+}
+
+/// Which pixels of the image are part of a match. Pixels where matches overlap are only counted
+/// once.
+pub fn covered(image: &BitGrid, matches: &[PatternMatch]) -> BitGrid {
+    let mut covered = BitGrid::new(image.width(), image.height());
+    for m in matches {
+        for &pos in &m.cells {
+            covered.set(pos, true);
+        }
+    }
+    covered
+}
+
+/// The image with `#` for set pixels, `O` for set pixels that are part of a match and `.` for
+/// others.
+pub fn highlight(image: &BitGrid, matches: &[PatternMatch]) -> String {
+    let covered = covered(image, matches);
+    Grid::from_fn(image.width(), image.height(), |pos| {
+        match (image.get(pos), covered.get(pos)) {
+            (true, true) => 'O',
+            (true, false) => '#',
+            (false, _) => '.',
+        }
+    })
+    .to_string()
+}
+
+/// The image with `#` for set pixels and `.` for others, like in the input.
+pub fn text(image: &BitGrid) -> String {
+    highlight(image, &[])
+}
+
+/// The image as a plain PBM (portable bitmap), with black for set pixels.
+pub fn pbm(image: &BitGrid) -> String {
+    let mut pbm = format!("P1\n{} {}\n", image.width(), image.height());
+    for y in 0..image.height() {
+        let mut row = (0..image.width()).map(|x| if image.get((x, y)) { "1" } else { "0" });
+        pbm.push_str(&row.join(" "));
+        pbm.push('\n');
+    }
+    pbm
+}
+
+/// The image as a plain PGM (portable graymap), with black for set pixels and gray for set
+/// pixels that are part of a match.
+pub fn pgm(image: &BitGrid, matches: &[PatternMatch]) -> String {
+    let covered = covered(image, matches);
+    let mut pgm = format!("P2\n{} {}\n2\n", image.width(), image.height());
+    for y in 0..image.height() {
+        let mut row = (0..image.width()).map(|x| match (image.get((x, y)), covered.get((x, y))) {
+            (true, true) => "1",
+            (true, false) => "0",
+            (false, _) => "2",
+        });
+        pgm.push_str(&row.join(" "));
+        pgm.push('\n');
+    }
+    pgm
+}
+
+/// Writes the image of the arrangement (as `image.txt`, `image.pbm` and `image.pgm` with the sea
+/// monsters in gray) and its map (`arrangement.txt`) to the directory.
+pub fn export(puzzle: &Puzzle, arrangement: &Arrangement, dir: &Path) -> Result<(), String> {
+    let image = arrangement.image(puzzle);
+    let matches = find_pattern(&image, &Pattern::parse(SEA_MONSTER));
+    fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+    let files = vec![
+        ("image.txt", text(&image)),
+        ("image.pbm", pbm(&image)),
+        ("image.pgm", pgm(&image, &matches)),
+        ("arrangement.txt", arrangement.map(puzzle)),
+    ];
+    for (name, content) in files {
+        let file = dir.join(name);
+        fs::write(&file, content)
+            .map_err(|e| format!("Could not write {}: {}", file.display(), e))?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    tiles: HashMap<u64, Tile>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tile {
+    pub id: u64,
+    content: BitGrid,
+    /// The sides in clockwise reading order: top, right, bottom, left.
+    sides: [u64; 4],
+    /// The result of [`Tile::side`] for each orientation, in the order of [`Orientation::all`].
+    oriented_sides: [[u64; 4]; 8],
+}
+
+impl Tile {
+    fn new(id: u64, content: BitGrid) -> Tile {
+        // Clockwise sides, with the first pixel in reading order in the highest bit
+        let length = content.width();
+        let last = length - 1;
+        let sides = [
+            reverse_bits(content.row_bits(0, 0, length), length),
+            reverse_bits(content.column_bits(last, 0, length), length),
+            content.row_bits(0, last, length),
+            content.column_bits(0, 0, length),
+        ];
+        let mut oriented_sides = [[0; 4]; 8];
+        for (orientation, oriented) in Orientation::all().zip(oriented_sides.iter_mut()) {
+            let clockwise = orientation.apply_edges(sides, length);
+            for (side, value) in oriented.iter_mut().enumerate() {
+                // The bottom and the left side are read the other way around
+                *value = if side >= 2 {
+                    reverse_bits(clockwise[side], length)
+                } else {
+                    clockwise[side]
+                };
+            }
+        }
+        Tile {
+            id,
+            content,
+            sides,
+            oriented_sides,
+        }
+    }
+
+    /// The sides as they are in the input, in clockwise reading order (top, right, bottom,
+    /// left) with the first pixel in the highest bit.
+    pub fn sides(&self) -> [u64; 4] {
+        self.sides
+    }
+
+    /// The side (0 top, 1 right, 2 bottom, 3 left) of the tile in the orientation, read from
+    /// left to right or top to bottom, so that it can be compared to the opposite side of a
+    /// neighbour.
+    fn side(&self, side: usize, orientation: Orientation) -> u64 {
+        self.oriented_sides[orientation.index()][side]
+    }
+
+    fn content(&self, orientation: Orientation) -> BitGrid {
+        orientation.apply_bits(&self.content)
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.content)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArrangedTile {
+    pub id: u64,
+    pub orientation: Orientation,
+}
+
+/// One of the eight symmetries of a square (the dihedral group D4): a flip around the vertical
+/// axis (or not), followed by a rotation counterclockwise by a multiple of 90 degrees.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Orientation {
+    /// Quarter turns counterclockwise, 0 to 3.
+    rotation: u8,
+    flip: bool,
+}
+
+impl Orientation {
+    pub const IDENTITY: Orientation = Orientation {
+        rotation: 0,
+        flip: false,
+    };
+
+    pub fn new(rotation: u8, flip: bool) -> Orientation {
+        assert!(rotation < 4, "Rotation must be 0 to 3, not {}", rotation);
+        Orientation { rotation, flip }
+    }
+
+    /// All eight, the rotations without flipping first.
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        [false, true]
+            .iter()
+            .flat_map(|&flip| (0..4).map(move |rotation| Orientation { rotation, flip }))
+    }
+
+    /// The position in [`Orientation::all`].
+    fn index(self) -> usize {
+        self.rotation as usize + if self.flip { 4 } else { 0 }
+    }
+
+    pub fn rotation(self) -> u8 {
+        self.rotation
+    }
+
+    pub fn flip(self) -> bool {
+        self.flip
+    }
+
+    /// This orientation followed by `other`.
+    pub fn then(self, other: Orientation) -> Orientation {
+        // Flipping and then rotating is the same as rotating the other way and then flipping
+        let rotation = if other.flip {
+            other.rotation + 4 - self.rotation
+        } else {
+            other.rotation + self.rotation
+        };
+        Orientation {
+            rotation: rotation % 4,
+            flip: self.flip ^ other.flip,
+        }
+    }
+
+    /// The orientation that undoes this one.
+    pub fn inverse(self) -> Orientation {
+        if self.flip {
+            // Flipping reverses the direction of the rotation, so this undoes itself
+            self
+        } else {
+            Orientation {
+                rotation: (4 - self.rotation) % 4,
+                flip: false,
+            }
+        }
+    }
+
+    /// The width and height of a grid after applying the orientation to it.
+    pub fn apply_size(self, (width, height): (usize, usize)) -> (usize, usize) {
+        if self.rotation.is_multiple_of(2) {
+            (width, height)
+        } else {
+            (height, width)
+        }
+    }
+
+    /// Where a position in a grid of the size ends up.
+    pub fn apply_pos(self, (mut x, mut y): Pos, (mut width, mut height): (usize, usize)) -> Pos {
+        if self.flip {
+            x = width - 1 - x;
+        }
+        for _ in 0..self.rotation {
+            let rotated = (y, width - 1 - x);
+            x = rotated.0;
+            y = rotated.1;
+            std::mem::swap(&mut width, &mut height);
+        }
+        (x, y)
+    }
+
+    pub fn apply_grid<T: Clone>(self, grid: &Grid<T>) -> Grid<T> {
+        let (width, height) = self.apply_size((grid.width(), grid.height()));
+        let inverse = self.inverse();
+        Grid::from_fn(width, height, |pos| {
+            grid[inverse.apply_pos(pos, (width, height))].clone()
+        })
+    }
+
+    /// Like [`Orientation::apply_grid`], but with the word-level transformations of [`BitGrid`].
+    pub fn apply_bits(self, grid: &BitGrid) -> BitGrid {
+        let flipped = if self.flip {
+            grid.flip_horizontal()
+        } else {
+            grid.clone()
+        };
+        match self.rotation {
+            0 => flipped,
+            1 => flipped.rotate_left(),
+            2 => flipped.flip_horizontal().flip_vertical(),
+            _ => flipped.transpose().flip_horizontal(),
+        }
+    }
+
+    /// The edges of a square with the given side length after applying the orientation, with
+    /// the edges as bits in clockwise reading order (top, right, bottom, left).
+    pub fn apply_edges(self, edges: [u64; 4], length: usize) -> [u64; 4] {
+        let mut result = [0; 4];
+        for (side, edge) in result.iter_mut().enumerate() {
+            let source = (side + self.rotation as usize) % 4;
+            *edge = if self.flip {
+                // Flipping swaps left and right, and reverses the reading order
+                reverse_bits(edges[(4 - source) % 4], length)
+            } else {
+                edges[source]
+            };
+        }
+        result
+    }
+}
+
+/// Reverses the lowest `length` bits.
+fn reverse_bits(bits: u64, length: usize) -> u64 {
+    bits.reverse_bits() >> (64 - length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = r#"Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...
+"#;
+
+        let puzzle = parse(input).unwrap();
+
+        // Tile 2311:
+        // ..##.#..#.
+        // ##..#.....
+        // #...##..#.
+        // ####.#...#
+        // ##.##.###.
+        // ##...#.###
+        // .#.#.#..##
+        // ..#....#..
+        // ###...#.#.
+        // ..###..###
+
+        let tile = &puzzle.tiles[&2311];
+        assert_eq!(tile.sides, [210, 89, 924, 318]);
+        assert_eq!(tile.side(0, Orientation::new(0, false)), 210);
+        assert_eq!(tile.side(1, Orientation::new(0, false)), 89);
+        assert_eq!(tile.side(2, Orientation::new(0, false)), 231);
+        assert_eq!(tile.side(3, Orientation::new(0, false)), 498);
+
+        assert_eq!(tile.side(0, Orientation::new(1, false)), 89);
+        assert_eq!(tile.side(1, Orientation::new(1, false)), 924);
+        assert_eq!(tile.side(2, Orientation::new(1, false)), 498);
+        assert_eq!(tile.side(3, Orientation::new(1, false)), 300);
+
+        assert_eq!(tile.side(0, Orientation::new(0, true)), 300);
+        assert_eq!(tile.side(1, Orientation::new(0, true)), 498);
+        assert_eq!(tile.side(2, Orientation::new(0, true)), 924);
+        assert_eq!(tile.side(3, Orientation::new(0, true)), 89);
+        let (result, image) = solve(&puzzle).unwrap();
+        assert_eq!(result, 20899048083289);
+        assert_eq!((image.width(), image.height()), (24, 24));
+
+        assert_eq!(solve2(&image), 273);
+        let matches = find_pattern(&image, &Pattern::parse(SEA_MONSTER));
+        assert_eq!(matches.len(), 2);
+        assert!(matches
+            .iter()
+            .all(|m| m.orientation == matches[0].orientation));
+        assert_eq!(highlight(&image, &matches).matches('O').count(), 30);
+
+        // The image is in whatever orientation the tiles were arranged in
+        let set = image.count_ones();
+        let text = text(&image);
+        assert_eq!(text.lines().count(), 24);
+        assert_eq!(text.matches('#').count(), set);
+        let pbm = pbm(&image);
+        assert!(pbm.starts_with("P1\n24 24\n"));
+        assert_eq!(pbm.lines().count(), 26);
+        assert_eq!(pbm.matches('1').count() - 1, set);
+        let pgm = pgm(&image, &matches);
+        assert!(pgm.starts_with("P2\n24 24\n2\n"));
+        let levels: Vec<&str> = pgm.lines().skip(3).flat_map(|l| l.split(' ')).collect();
+        assert_eq!(levels.len(), 24 * 24);
+        assert_eq!(levels.iter().filter(|&&l| l == "0").count(), 273);
+        assert_eq!(levels.iter().filter(|&&l| l == "1").count(), 30);
+
+        let arrangement = assemble(&puzzle).unwrap();
+        let map = arrangement.map(&puzzle);
+        assert_eq!(map.lines().count(), 10);
+        // Neighbours have the same sides
+        let sides: Vec<Vec<u64>> = map
+            .lines()
+            .skip(1)
+            .map(|l| l.split(' ').skip(5).map(|n| n.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(sides[0][1], sides[1][3]);
+        assert_eq!(sides[0][2], sides[3][0]);
+
+        // The first two rows and the first row of the arrangement
+        let rectangle = |ids: &[u64]| Puzzle {
+            tiles: ids
+                .iter()
+                .map(|id| (*id, puzzle.tiles[id].clone()))
+                .collect(),
+        };
+        let (result, image) = solve(&rectangle(&[1951, 2311, 3079, 2729, 1427, 2473])).unwrap();
+        assert_eq!(result, 1951 * 3079 * 2729 * 2473);
+        assert_eq!((image.width(), image.height()), (24, 16));
+        let (result, image) = solve(&rectangle(&[1951, 2311, 3079])).unwrap();
+        assert_eq!(result, 1951 * 3079);
+        assert_eq!((image.width(), image.height()), (24, 8));
+    }
+
+    /// Cuts a random image into `width` by `height` tiles with `length` pixels per side, which
+    /// share their sides with their neighbours, and rotates and flips each one. Also returns the
+    /// image without the borders of the tiles.
+    fn synthetic(width: usize, height: usize, length: usize) -> (Puzzle, BitGrid) {
+        let mut state = 2020u64;
+        let mut random = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            state >> 33
+        };
+        let step = length - 1;
+        let full = BitGrid::from_fn(width * step + 1, height * step + 1, |_| random() % 2 == 0);
+        let mut tiles = HashMap::new();
+        for (y, x) in (0..height).cartesian_product(0..width) {
+            let id = (1000 + y * width + x) as u64;
+            let content = BitGrid::from_fn(length, length, |(px, py)| {
+                full.get((x * step + px, y * step + py))
+            });
+            let orientation = Orientation::all().nth(random() as usize % 8).unwrap();
+            tiles.insert(id, Tile::new(id, orientation.apply_bits(&content)));
+        }
+        let inner = length - 2;
+        let image = BitGrid::from_fn(width * inner, height * inner, |(x, y)| {
+            full.get((
+                x / inner * step + 1 + x % inner,
+                y / inner * step + 1 + y % inner,
+            ))
+        });
+        (Puzzle { tiles }, image)
+    }
+
+    #[test]
+    fn test_synthetic() {
+        let (puzzle, expected) = synthetic(16, 12, 24);
+        let (result, image) = solve(&puzzle).unwrap();
+        assert_eq!(result, 1000 * 1015 * 1176 * 1191);
+        // The whole image can end up in any orientation
+        assert!(Orientation::all().any(|o| o.apply_bits(&expected) == image));
+    }
+
+    #[test]
+    fn test_assemble_all() {
+        let blank = |ids: &[u64]| Puzzle {
+            tiles: ids
+                .iter()
+                .map(|&id| (id, Tile::new(id, BitGrid::new(3, 3))))
+                .collect(),
+        };
+        // All orientations of a single tile are the same up to symmetry
+        let assembly = assemble_all(&blank(&[1]), 10);
+        assert_eq!(assembly.arrangements.len(), 1);
+        assert!(assembly.complete);
+        // 2 positions for the first tile, 8 orientations each, and 4 symmetries of a 2x1 image
+        let assembly = assemble_all(&blank(&[1, 2]), 100);
+        assert_eq!(assembly.arrangements.len(), 2 * 8 * 8 / 4);
+        assert!(assembly.complete);
+        let assembly = assemble_all(&blank(&[1, 2, 3, 4]), 5);
+        assert_eq!(assembly.arrangements.len(), 5);
+        assert!(!assembly.complete);
+
+        let (puzzle, _) = synthetic(5, 4, 24);
+        let assembly = assemble_all(&puzzle, 10);
+        assert_eq!(assembly.arrangements.len(), 1);
+        assert!(assembly.complete);
+        let arrangement = &assembly.arrangements[0];
+        for orientation in Orientation::all() {
+            assert_eq!(
+                arrangement.transform(orientation).image(&puzzle),
+                orientation.apply_bits(&arrangement.image(&puzzle))
+            );
+        }
+
+        // A tile that doesn't fit anywhere
+        let mut puzzle = puzzle;
+        puzzle
+            .tiles
+            .insert(1000, Tile::new(1000, BitGrid::new(24, 24)));
+        let assembly = assemble_all(&puzzle, 10);
+        assert!(assembly.arrangements.is_empty());
+        assert!(assembly.complete);
+        assert!(assemble(&puzzle).is_none());
+
+        let puzzle = parse("Tile 1:\n###\n###\n###\n\nTile 2:\n...\n...\n...\n").unwrap();
+        assert_eq!(
+            Day20::part1(&puzzle).to_string(),
+            "no answer: the tiles can't be arranged in a rectangle"
+        );
+        assert_eq!(Day20::part2(&puzzle), no_arrangement());
+    }
+
+    #[test]
+    fn test_tile_sizes() {
+        let puzzle = parse("Tile 1:\n#..\n.#.\n..#\n\nTile 2:\n###\n..#\n#..\n").unwrap();
+        assert_eq!(puzzle.tiles[&2].sides, [7, 6, 1, 5]);
+        let puzzle = parse("Tile 1:\n#..\n.#.\n..#\n\nTile 2:\n###\n..#\n#..\n\n").unwrap();
+        assert_eq!(puzzle.tiles.len(), 2);
+        assert_eq!(parse("\n").unwrap_err().expected, "a tile");
+
+        let error = parse("Tile 1:\n#..\n.#.\n..#\n\nTile 2:\n#...\n....\n....\n...#").unwrap_err();
+        assert_eq!((error.line, error.column), (7, 1));
+        assert_eq!(error.expected, "a tile of 3x3 pixels like the first one");
+
+        let error = parse("Tile 1:\n#..\n.#.").unwrap_err();
+        assert_eq!(error.expected, "a square tile");
+        let error = parse("Tile 1:\n#.\n.#").unwrap_err();
+        assert_eq!(error.expected, "a tile of at least 3x3 pixels");
+    }
+
+    #[test]
+    fn test_pattern() {
+        let monster = Pattern::parse(SEA_MONSTER);
+        assert_eq!(
+            (monster.width(), monster.height(), monster.len()),
+            (20, 3, 15)
+        );
+
+        let image = |s: &str| {
+            let text = Text::new(20, s);
+            BitGrid::from(&Grid::parse(&text, s, "`#` or `.`", |c| Some(c == '#')).unwrap())
+        };
+        // An L in two orientations, sharing the corner
+        let l = Pattern::parse("#\n##");
+        let img = image("##.\n#..\n...");
+        let matches = find_pattern(&img, &l);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].x, matches[0].y), (0, 0));
+
+        let img = image("....\n.#..\n.###\n...#");
+        let matches = find_pattern(&img, &l);
+        let found: Vec<_> = matches.iter().map(|m| (m.x, m.y)).collect();
+        assert_eq!(found, vec![(1, 1), (2, 2)]);
+        // The overlapping pixel only counts once
+        assert_eq!(covered(&img, &matches).count_ones(), 5);
+        assert_eq!(highlight(&img, &matches), "....\n.O..\n.OOO\n...O\n");
+
+        // Symmetric patterns are found once per place
+        let line = Pattern::parse("###");
+        assert_eq!(find_pattern(&image("###\n..."), &line).len(), 1);
+    }
+
+    #[test]
+    fn test_orientation() {
+        // Not symmetric in any way, and not square
+        let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]);
+        let all: Vec<Orientation> = Orientation::all().collect();
+        assert_eq!(all.len(), 8);
+        let images: HashSet<Grid<i32>> = all.iter().map(|o| o.apply_grid(&grid)).collect();
+        assert_eq!(images.len(), 8);
+
+        // Counterclockwise
+        let rotated = Orientation::new(1, false).apply_grid(&grid);
+        assert_eq!(rotated, Grid::new(2, 3, vec![3, 6, 2, 5, 1, 4]));
+        let flipped = Orientation::new(0, true).apply_grid(&grid);
+        assert_eq!(flipped, Grid::new(3, 2, vec![3, 2, 1, 6, 5, 4]));
+
+        for &a in &all {
+            assert_eq!(a.then(a.inverse()), Orientation::IDENTITY);
+            assert_eq!(a.inverse().apply_grid(&a.apply_grid(&grid)), grid);
+            for &b in &all {
+                assert_eq!(
+                    a.then(b).apply_grid(&grid),
+                    b.apply_grid(&a.apply_grid(&grid))
+                );
+            }
+            let image = a.apply_grid(&grid);
+            for (pos, value) in grid.iter() {
+                assert_eq!(&image[a.apply_pos(pos, (3, 2))], value);
+            }
+            let bits = grid.map(|&v| v % 2 == 0);
+            assert_eq!(
+                a.apply_bits(&BitGrid::from(&bits)),
+                BitGrid::from(&a.apply_grid(&bits))
+            );
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        let content = Grid::new(
+            3,
+            3,
+            vec![true, true, false, false, false, true, true, false, false],
+        );
+        let tile = Tile::new(1, BitGrid::from(&content));
+        for orientation in Orientation::all() {
+            let expected = Tile::new(1, tile.content(orientation)).sides;
+            assert_eq!(orientation.apply_edges(tile.sides, 3), expected);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! https://adventofcode.com/2020/day/21
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        solve2(puzzle)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day21::DAY, input);
+    let mut foods = Vec::new();
+
+    for line in text.lines() {
+        let (ingredients, allergens) = text.split_once(line, " (contains ")?;
+        let allergens = text.strip_suffix(allergens, ")")?;
+        let ingredients = ingredients.split(' ').map(|s| s.to_string()).collect();
+        let allergens = allergens.split(", ").map(|s| s.to_string()).collect();
+        foods.push(Food {
+            ingredients,
+            allergens,
+        });
+    }
+
+    Ok(Puzzle { foods })
+}
+
+pub fn solve(puzzle: &Puzzle) -> usize {
+    let mut assignment: HashMap<&String, HashSet<&String>> = HashMap::new();
+    for food in &puzzle.foods {
+        for allergen in &food.allergens {
+            if let Some(ing) = assignment.get_mut(allergen) {
+                ing.retain(|k| food.ingredients.contains(k));
+            } else {
+                assignment.insert(allergen, food.ingredients.iter().collect());
+            }
+        }
+    }
+
+    let all_allergic_foods: HashSet<_> = assignment.values().flatten().collect();
+    puzzle
+        .foods
+        .iter()
+        .flat_map(|f| &f.ingredients)
+        .filter(|i| !all_allergic_foods.contains(&i))
+        .count()
+}
+
+pub fn solve2(puzzle: &Puzzle) -> String {
+    let mut allergen_to_ing: HashMap<&String, HashSet<&String>> = HashMap::new();
+    for food in &puzzle.foods {
+        for allergen in &food.allergens {
+            if let Some(ing) = allergen_to_ing.get_mut(allergen) {
+                ing.retain(|k| food.ingredients.contains(k));
+            } else {
+                allergen_to_ing.insert(allergen, food.ingredients.iter().collect());
+            }
+        }
+    }
+
+    let mut assignment: BTreeMap<&String, &String> = BTreeMap::new();
+    loop {
+        let allergen_with_single = allergen_to_ing
+            .iter()
+            .find(|(_k, v)| v.len() == 1)
+            .map(|(k, _v)| *k);
+        if let Some(allergen) = allergen_with_single {
+            if let Some(single_ingredient) = allergen_to_ing.remove(allergen) {
+                let ingredient = single_ingredient.into_iter().next().unwrap();
+                assignment.insert(allergen, ingredient);
+                for ingredients in allergen_to_ing.values_mut() {
+                    ingredients.remove(ingredient);
+                }
+            }
+        } else {
+            break;
+        }
+    }
+
+    assignment.values().join(",")
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    foods: Vec<Food>,
+}
+
+#[derive(Clone, Debug)]
+struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = r#"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)"#;
+
+        let puzzle = parse(input).unwrap();
+
+        assert_eq!(solve(&puzzle), 5);
+        assert_eq!(solve2(&puzzle), "mxmxvkd,sqjhc,fvjkl".to_string());
+    }
+}
@@ -0,0 +1,145 @@
+//! https://adventofcode.com/2020/day/22
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use std::collections::{HashSet, LinkedList};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle.one.clone(), puzzle.two.clone())
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        solve2(puzzle.one.clone(), puzzle.two.clone()).1
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    one: LinkedList<usize>,
+    two: LinkedList<usize>,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day22::DAY, input);
+    let (one, two) = text.split_once(input, "\n\n")?;
+    let one = parse_nums(&text, text.strip_prefix(one, "Player 1:\n")?)?;
+    let two = parse_nums(&text, text.strip_prefix(two, "Player 2:\n")?)?;
+    Ok(Puzzle { one, two })
+}
+
+fn parse_nums(text: &Text, input: &str) -> Result<LinkedList<usize>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|s| text.parse::<usize>(s, "a card number"))
+        .collect()
+}
+
+pub fn solve(mut one: LinkedList<usize>, mut two: LinkedList<usize>) -> usize {
+    while !(one.is_empty() || one.is_empty()) {
+        let a = one.pop_front().unwrap();
+        let b = two.pop_front().unwrap();
+        if a > b {
+            one.push_back(a);
+            one.push_back(b);
+        } else {
+            two.push_back(b);
+            two.push_back(a);
+        }
+    }
+
+    if !one.is_empty() {
+        score(&one)
+    } else {
+        score(&two)
+    }
+}
+
+pub fn solve2(mut one: LinkedList<usize>, mut two: LinkedList<usize>) -> (usize, usize) {
+    let mut previous = HashSet::new();
+
+    while !(one.is_empty() || two.is_empty()) {
+        let state = (one.clone(), two.clone());
+        if previous.contains(&state) {
+            return (0, 0);
+        } else {
+            previous.insert(state);
+        }
+
+        let a = one.pop_front().unwrap();
+        let b = two.pop_front().unwrap();
+
+        let winner = if one.len() >= a && two.len() >= b {
+            // recurse
+            let new_one = one.iter().copied().take(a).collect();
+            let new_two = two.iter().copied().take(b).collect();
+            let (winner, _score) = solve2(new_one, new_two);
+            winner
+        } else if a > b {
+            0
+        } else {
+            1
+        };
+
+        if winner == 0 {
+            one.push_back(a);
+            one.push_back(b);
+        } else {
+            two.push_back(b);
+            two.push_back(a);
+        }
+    }
+
+    if !one.is_empty() {
+        (0, score(&one))
+    } else {
+        (1, score(&two))
+    }
+}
+
+fn score(cards: &LinkedList<usize>) -> usize {
+    cards
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| (i + 1) * c)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = r#"Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10
+"#;
+
+        let Puzzle { one, two } = parse(input).unwrap();
+        assert_eq!(solve(one.clone(), two.clone()), 306);
+        assert_eq!(solve2(one.clone(), two.clone()).1, 291);
+    }
+}
@@ -0,0 +1,192 @@
+//! https://adventofcode.com/2020/day/23
+
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use intrusive_collections::{intrusive_adapter, LinkedList, LinkedListLink};
+use itertools::Itertools;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input = Vec<usize>;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Self::Answer1 {
+        solve(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Self::Answer2 {
+        solve2(nums)
+    }
+}
+
+pub fn solve2(nums: &[usize]) -> usize {
+    let mut nums = nums.to_vec();
+    let max_input = *nums.iter().max().unwrap();
+
+    let mut num = max_input + 1;
+    while nums.len() != 1_000_000 {
+        nums.push(num);
+        num += 1;
+    }
+
+    play2(&nums, 10_000_000)
+}
+
+fn play2(nums: &[usize], moves: i32) -> usize {
+    let min = *nums.iter().min().unwrap();
+    let max = *nums.iter().max().unwrap();
+
+    // Our numbers start at 1, so we need one additional slot.
+    let mut next = vec![0; nums.len() + 1];
+    for (&c, &n) in nums.iter().tuple_windows() {
+        next[c] = n;
+    }
+    next[nums[nums.len() - 1]] = nums[0];
+
+    let mut current = nums[0];
+    for _ in 0..moves {
+        let a = next[current];
+        let b = next[a];
+        let c = next[b];
+
+        let dest = find_destination(current, min, max, a, b, c);
+        let after_dest = next[dest];
+        let after_c = next[c];
+
+        next[dest] = a;
+        next[c] = after_dest;
+        next[current] = after_c;
+
+        current = after_c;
+    }
+
+    let a = next[1];
+    let b = next[a];
+
+    a * b
+}
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let text = Text::new(Day23::DAY, input);
+    let cups: Vec<_> = text.chars(input.trim()).collect();
+    let mut nums = Vec::new();
+    for &(c, part) in &cups {
+        match c.to_digit(10) {
+            Some(d) if d > 0 && d as usize <= cups.len() && !nums.contains(&(d as usize)) => {
+                nums.push(d as usize)
+            }
+            _ => {
+                let expected = format!("a digit from 1 to {} that isn't used yet", cups.len());
+                return Err(text.error(part, &expected));
+            }
+        }
+    }
+    // Each move takes out the current cup and the next three, so the destination needs a fifth.
+    if nums.len() < 5 {
+        return Err(text.error_after(input.trim(), "at least 5 cups"));
+    }
+    Ok(nums)
+}
+
+pub fn solve(nums: &[usize]) -> String {
+    let result = play(nums, 100);
+
+    let result = result.iter().join("");
+    let parts: Vec<_> = result.split('1').collect();
+    parts.iter().rev().join("")
+}
+
+#[derive(Clone)]
+struct Cup {
+    link: LinkedListLink,
+    value: usize,
+}
+
+intrusive_adapter!(CupAdapter = Box<Cup>: Cup { link: LinkedListLink });
+
+fn play(nums: &[usize], moves: usize) -> Vec<usize> {
+    let mut list = LinkedList::new(CupAdapter::new());
+    for &num in nums {
+        list.push_back(Box::new(Cup {
+            link: LinkedListLink::new(),
+            value: num,
+        }));
+    }
+    let min = *nums.iter().min().unwrap();
+    let max = *nums.iter().max().unwrap();
+
+    for _ in 0..moves {
+        let current = list.pop_front().unwrap();
+        let a = list.pop_front().unwrap();
+        let b = list.pop_front().unwrap();
+        let c = list.pop_front().unwrap();
+
+        let dest = find_destination(current.value, min, max, a.value, b.value, c.value);
+        let mut cursor = list.back_mut();
+        loop {
+            if let Some(cup) = cursor.get() {
+                if cup.value == dest {
+                    cursor.insert_after(c);
+                    cursor.insert_after(b);
+                    cursor.insert_after(a);
+                    break;
+                }
+            }
+            cursor.move_prev();
+        }
+
+        list.push_back(current);
+    }
+
+    list.iter().map(|cup| cup.value).collect()
+}
+
+fn find_destination(current: usize, min: usize, max: usize, a: usize, b: usize, c: usize) -> usize {
+    let mut dest = current - 1;
+    loop {
+        if dest < min {
+            dest = max;
+        }
+        if !(dest == a || dest == b || dest == c || dest == current) {
+            return dest;
+        }
+        dest -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(solve(&parse("389125467").unwrap()), "67384529");
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(solve2(&parse("389125467").unwrap()), 149245887792);
+    }
+
+    #[test]
+    fn test_unusable() {
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "at least 5 cups");
+
+        let error = parse("38912546").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.expected, "a digit from 1 to 8 that isn't used yet");
+
+        let error = parse("3142").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.expected, "at least 5 cups");
+    }
+}
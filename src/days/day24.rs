@@ -0,0 +1,180 @@
+//! https://adventofcode.com/2020/day/24
+
+use crate::automaton::{Automaton, Rule};
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+use std::collections::HashSet;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle)
+    }
+
+    fn part2(puzzle: &Self::Input) -> Self::Answer2 {
+        solve2(puzzle)
+    }
+}
+
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+}
+
+struct Instruction {
+    directions: Vec<Direction>,
+}
+
+enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day24::DAY, input);
+    let instructions = text
+        .lines()
+        .map(|line| parse_line(&text, line))
+        .collect::<Result<_, _>>()?;
+    Ok(Puzzle { instructions })
+}
+
+fn parse_line(text: &Text, line: &str) -> Result<Instruction, ParseError> {
+    let mut s = line;
+    let mut directions = Vec::new();
+    while !s.is_empty() {
+        if s.starts_with("e") {
+            directions.push(Direction::East);
+            s = &s[1..];
+        } else if s.starts_with("w") {
+            directions.push(Direction::West);
+            s = &s[1..];
+        } else if s.starts_with("se") {
+            directions.push(Direction::SouthEast);
+            s = &s[2..];
+        } else if s.starts_with("sw") {
+            directions.push(Direction::SouthWest);
+            s = &s[2..];
+        } else if s.starts_with("ne") {
+            directions.push(Direction::NorthEast);
+            s = &s[2..];
+        } else if s.starts_with("nw") {
+            directions.push(Direction::NorthWest);
+            s = &s[2..];
+        } else {
+            return Err(text.error(s, "`e`, `se`, `sw`, `w`, `nw` or `ne`"));
+        }
+    }
+    Ok(Instruction { directions })
+}
+
+pub fn solve(puzzle: &Puzzle) -> usize {
+    flip(puzzle).len()
+}
+
+pub fn solve2(puzzle: &Puzzle) -> usize {
+    // Black tiles are live: a white tile with 2 black neighbours flips to black, a black tile
+    // with 0 or more than 2 black neighbours flips to white.
+    let automaton = Automaton::new(neighbors, Rule::new(vec![2], vec![1, 2]));
+    automaton.run(flip(puzzle), 100).live.len()
+}
+
+fn neighbors(&(x, y): &(i64, i64)) -> Vec<(i64, i64)> {
+    vec![
+        (x - 2, y),
+        (x + 2, y),
+        (x - 1, y - 1),
+        (x - 1, y + 1),
+        (x + 1, y - 1),
+        (x + 1, y + 1),
+    ]
+}
+
+fn flip(puzzle: &Puzzle) -> HashSet<(i64, i64)> {
+    let mut flipped = HashSet::new();
+    for instruction in &puzzle.instructions {
+        let mut x = 0i64;
+        let mut y = 0i64;
+        for direction in &instruction.directions {
+            match direction {
+                Direction::East => x += 2,
+                Direction::SouthEast => {
+                    x += 1;
+                    y += 1;
+                }
+                Direction::SouthWest => {
+                    x -= 1;
+                    y += 1;
+                }
+                Direction::West => {
+                    x -= 2;
+                }
+                Direction::NorthWest => {
+                    x -= 1;
+                    y -= 1;
+                }
+                Direction::NorthEast => {
+                    x += 1;
+                    y -= 1;
+                }
+            }
+        }
+        if !flipped.insert((x, y)) {
+            flipped.remove(&(x, y));
+        }
+    }
+    flipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let input = r#"sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew"#;
+
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve(&puzzle), 10);
+        assert_eq!(solve2(&puzzle), 2208);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("esew\nnwwe\nsenx").err().unwrap();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.found, "\"nx\"");
+    }
+}
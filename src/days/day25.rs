@@ -0,0 +1,101 @@
+//! https://adventofcode.com/2020/day/25
+
+use crate::modular::{discrete_log, mod_pow};
+use crate::parse::{ParseError, Text};
+use crate::Solution;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    type Input = Puzzle;
+    type Answer1 = u64;
+    type Answer2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Self::Input) -> Self::Answer1 {
+        solve(puzzle)
+    }
+
+    fn part2(_puzzle: &Self::Input) -> Self::Answer2 {
+        // There's no puzzle for part 2 on the last day.
+        "-"
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    key1: u64,
+    key2: u64,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let text = Text::new(Day25::DAY, input);
+    let mut lines = text.lines();
+    let mut next_key = || match lines.next() {
+        Some(line) => text.parse::<u64>(line, "a public key"),
+        None => Err(text.error_at_end("a public key")),
+    };
+    let key1 = next_key()?;
+    let key2 = next_key()?;
+    Ok(Puzzle { key1, key2 })
+}
+
+/// The modulus of the transformations.
+const MODULUS: u64 = 20201227;
+
+/// The subject number that the public keys are transformed from.
+const SUBJECT_NUMBER: u64 = 7;
+
+pub fn solve(puzzle: &Puzzle) -> u64 {
+    let loop_size2 = find_loop_size(SUBJECT_NUMBER, puzzle.key2, MODULUS)
+        .unwrap_or_else(|| panic!("No loop size gives the public key {}", puzzle.key2));
+
+    transform(puzzle.key1, loop_size2, MODULUS)
+}
+
+fn transform(subject_number: u64, loop_size: u64, modulus: u64) -> u64 {
+    mod_pow(subject_number, loop_size, modulus)
+}
+
+/// The loop size that transforms the subject number to the key, `None` if there's none.
+fn find_loop_size(subject_number: u64, key: u64, modulus: u64) -> Option<u64> {
+    if key >= modulus {
+        return None;
+    }
+    discrete_log(subject_number, key, modulus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(find_loop_size(7, 5764801, MODULUS), Some(8));
+        assert_eq!(find_loop_size(7, 17807724, MODULUS), Some(11));
+        assert_eq!(transform(17807724, 8, MODULUS), 14897079);
+        // Keys must be smaller than the modulus
+        assert_eq!(find_loop_size(7, MODULUS + 1, MODULUS), None);
+
+        let input = "5764801\n17807724";
+
+        let puzzle = parse(input).unwrap();
+        assert_eq!(solve(&puzzle), 14897079);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("5764801\n1780x7724").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a public key");
+        assert_eq!(error.found, "\"1780x7724\"");
+
+        let error = parse("5764801\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "end of input");
+    }
+}
@@ -0,0 +1,126 @@
+//! One module per day, see https://adventofcode.com/2020
+
+use crate::bench::Timing;
+use crate::parse::ParseError;
+use crate::Solution;
+use std::hint::black_box;
+use std::time::Instant;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// A day's [`Solution`] with its types erased, so that the runner can work with all days.
+pub struct Day {
+    pub day: u32,
+    /// Parses the input and returns the answers for part 1 and 2 with how long each step took.
+    pub run: fn(&str) -> Result<Output, ParseError>,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Day {
+        Day {
+            day: S::DAY,
+            run: run::<S>,
+        }
+    }
+}
+
+/// The answers of a day and how long it took to get them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Output {
+    pub one: String,
+    pub two: String,
+    pub timing: Timing,
+}
+
+fn run<S: Solution>(input: &str) -> Result<Output, ParseError> {
+    // Converting the answers to strings is not part of the timing
+    let start = Instant::now();
+    let input = black_box(S::parse(black_box(input))?);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let one = black_box(S::part1(&input));
+    let part1 = start.elapsed();
+
+    let start = Instant::now();
+    let two = black_box(S::part2(&input));
+    let part2 = start.elapsed();
+
+    Ok(Output {
+        one: one.to_string(),
+        two: two.to_string(),
+        timing: Timing {
+            parse,
+            part1,
+            part2,
+        },
+    })
+}
+
+pub static DAYS: [Day; 25] = [
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+    Day::of::<day05::Day05>(),
+    Day::of::<day06::Day06>(),
+    Day::of::<day07::Day07>(),
+    Day::of::<day08::Day08>(),
+    Day::of::<day09::Day09>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day13::Day13>(),
+    Day::of::<day14::Day14>(),
+    Day::of::<day15::Day15>(),
+    Day::of::<day16::Day16>(),
+    Day::of::<day17::Day17>(),
+    Day::of::<day18::Day18>(),
+    Day::of::<day19::Day19>(),
+    Day::of::<day20::Day20>(),
+    Day::of::<day21::Day21>(),
+    Day::of::<day22::Day22>(),
+    Day::of::<day23::Day23>(),
+    Day::of::<day24::Day24>(),
+    Day::of::<day25::Day25>(),
+];
+
+pub fn get(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.day, i as u32 + 1);
+        }
+    }
+}
@@ -0,0 +1,503 @@
+//! Grammars like the message rules of day 19: numbered rules that match a character, a sequence
+//! of other rules, or one of several alternatives.
+//!
+//! Besides matching messages, the rules can be analysed (undefined and unreachable rules,
+//! recursion, the lengths of the strings they match) and exported as EBNF or as a Graphviz graph.
+
+use itertools::Itertools;
+use petgraph::algo::tarjan_scc;
+use petgraph::graphmap::DiGraphMap;
+use petgraph::visit::Dfs;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rule {
+    Char(char),
+    Alt(Vec<Rule>),
+    List(Vec<usize>),
+}
+
+/// The rules by their number.
+pub type Rules = HashMap<usize, Rule>;
+
+impl Rule {
+    /// The numbers of the rules it refers to, in order and with duplicates.
+    pub fn references(&self) -> Vec<usize> {
+        self.alternatives()
+            .into_iter()
+            .flatten()
+            .filter_map(|symbol| match symbol {
+                Symbol::Rule(n) => Some(n),
+                Symbol::Char(_) => None,
+            })
+            .collect()
+    }
+
+    /// The alternatives of the rule, each a sequence of symbols.
+    fn alternatives(&self) -> Vec<Vec<Symbol>> {
+        match self {
+            Rule::Char(c) => vec![vec![Symbol::Char(*c)]],
+            Rule::List(list) => vec![list.iter().map(|&n| Symbol::Rule(n)).collect()],
+            Rule::Alt(alts) => alts.iter().flat_map(Rule::alternatives).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Symbol {
+    Char(char),
+    Rule(usize),
+}
+
+/// Decides whether a message matches a rule, for any grammar, including recursive ones.
+/// Undefined rules don't match anything.
+///
+/// This is an Earley recognizer: For each position in the message, it keeps the set of partial
+/// matches (items) that are possible there, each an alternative of a rule with how many of its
+/// symbols are matched so far and where the match started. Rules can't match the empty string,
+/// which keeps completion simple.
+pub struct Matcher {
+    /// For each rule, its alternatives, each a sequence of symbols.
+    alternatives: HashMap<usize, Vec<Vec<Symbol>>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Item {
+    rule: usize,
+    alternative: usize,
+    /// How many symbols of the alternative are matched.
+    dot: usize,
+    /// Where the match of the rule started.
+    origin: usize,
+}
+
+impl Matcher {
+    pub fn new(rules: &Rules) -> Matcher {
+        let alternatives = rules
+            .iter()
+            .map(|(&num, rule)| (num, rule.alternatives()))
+            .collect();
+        Matcher { alternatives }
+    }
+
+    /// Undefined rules don't match anything.
+    fn alternatives_of(&self, rule: usize) -> &[Vec<Symbol>] {
+        self.alternatives.get(&rule).map_or(&[], |a| a)
+    }
+
+    fn symbol(&self, item: &Item) -> Option<Symbol> {
+        self.alternatives[&item.rule][item.alternative]
+            .get(item.dot)
+            .copied()
+    }
+
+    pub fn matches(&self, rule: usize, message: &str) -> bool {
+        let chars: Vec<char> = message.chars().collect();
+        let mut sets: Vec<Vec<Item>> = vec![Vec::new(); chars.len() + 1];
+        let mut seen: Vec<HashSet<Item>> = vec![HashSet::new(); chars.len() + 1];
+        let add = |sets: &mut Vec<Vec<Item>>, seen: &mut Vec<HashSet<Item>>, pos: usize, item| {
+            if seen[pos].insert(item) {
+                sets[pos].push(item);
+            }
+        };
+
+        for alternative in 0..self.alternatives_of(rule).len() {
+            let item = Item {
+                rule,
+                alternative,
+                dot: 0,
+                origin: 0,
+            };
+            add(&mut sets, &mut seen, 0, item);
+        }
+
+        for pos in 0..=chars.len() {
+            // Items are added to the current set while processing it
+            let mut i = 0;
+            while i < sets[pos].len() {
+                let item = sets[pos][i];
+                i += 1;
+                let advanced = Item {
+                    dot: item.dot + 1,
+                    ..item
+                };
+                match self.symbol(&item) {
+                    // Scan
+                    Some(Symbol::Char(c)) => {
+                        if chars.get(pos) == Some(&c) {
+                            add(&mut sets, &mut seen, pos + 1, advanced);
+                        }
+                    }
+                    // Predict
+                    Some(Symbol::Rule(r)) => {
+                        for alternative in 0..self.alternatives_of(r).len() {
+                            let predicted = Item {
+                                rule: r,
+                                alternative,
+                                dot: 0,
+                                origin: pos,
+                            };
+                            add(&mut sets, &mut seen, pos, predicted);
+                        }
+                    }
+                    // Complete: advance the items that were waiting for this rule. They are in
+                    // an earlier set that's done, because the match can't be empty.
+                    None => {
+                        let waiting: Vec<Item> = sets[item.origin]
+                            .iter()
+                            .filter(|w| self.symbol(w) == Some(Symbol::Rule(item.rule)))
+                            .map(|w| Item {
+                                dot: w.dot + 1,
+                                ..*w
+                            })
+                            .collect();
+                        for w in waiting {
+                            add(&mut sets, &mut seen, pos, w);
+                        }
+                    }
+                }
+            }
+        }
+
+        sets[chars.len()]
+            .iter()
+            .any(|item| item.rule == rule && item.origin == 0 && self.symbol(item).is_none())
+    }
+}
+
+/// What's known about a grammar, see [`Analysis::new`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Analysis {
+    pub start: usize,
+    /// Rules that are referenced but not defined, with the rules that reference them.
+    pub undefined: BTreeMap<usize, BTreeSet<usize>>,
+    /// Defined rules that can't be reached from the start rule.
+    pub unreachable: BTreeSet<usize>,
+    /// Rules that refer to themselves, directly or through other rules.
+    pub recursive: BTreeSet<usize>,
+    /// Rules that refer to themselves at the start of an alternative, directly or through other
+    /// rules. These can't be matched by a naive recursive descent.
+    pub left_recursive: BTreeSet<usize>,
+    /// The lengths of the strings the defined rules match, up to `max_length`.
+    pub lengths: BTreeMap<usize, Lengths>,
+    pub max_length: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lengths {
+    pub lengths: BTreeSet<usize>,
+    /// Whether the rule can match longer strings than the maximum length, because it can reach
+    /// a recursive rule.
+    pub unbounded: bool,
+}
+
+impl Analysis {
+    /// Analyses the rules as reached from the start rule, computing lengths up to `max_length`
+    /// (e.g. the length of the longest message).
+    pub fn new(rules: &Rules, start: usize, max_length: usize) -> Analysis {
+        let mut graph = DiGraphMap::new();
+        // Only the edges to the first rule of each alternative
+        let mut left_graph = DiGraphMap::new();
+        let mut undefined: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for (&num, rule) in rules {
+            graph.add_node(num);
+            left_graph.add_node(num);
+            for reference in rule.references() {
+                graph.add_edge(num, reference, ());
+                if !rules.contains_key(&reference) {
+                    undefined.entry(reference).or_default().insert(num);
+                }
+            }
+            for alternative in rule.alternatives() {
+                if let Some(&Symbol::Rule(first)) = alternative.first() {
+                    left_graph.add_edge(num, first, ());
+                }
+            }
+        }
+
+        let mut reachable = HashSet::new();
+        if graph.contains_node(start) {
+            let mut dfs = Dfs::new(&graph, start);
+            while let Some(num) = dfs.next(&graph) {
+                reachable.insert(num);
+            }
+        }
+        let unreachable = rules
+            .keys()
+            .filter(|num| !reachable.contains(num))
+            .copied()
+            .collect();
+
+        let recursive = cyclic(&graph);
+        let lengths = lengths(rules, max_length)
+            .into_iter()
+            .map(|(num, lengths)| {
+                let mut dfs = Dfs::new(&graph, num);
+                let mut unbounded = false;
+                while let Some(n) = dfs.next(&graph) {
+                    unbounded |= recursive.contains(&n);
+                }
+                (num, Lengths { lengths, unbounded })
+            })
+            .collect();
+
+        Analysis {
+            start,
+            undefined,
+            unreachable,
+            left_recursive: cyclic(&left_graph),
+            recursive,
+            lengths,
+            max_length,
+        }
+    }
+}
+
+/// The nodes that are part of a cycle.
+fn cyclic(graph: &DiGraphMap<usize, ()>) -> BTreeSet<usize> {
+    tarjan_scc(graph)
+        .into_iter()
+        .filter(|component| component.len() > 1 || graph.contains_edge(component[0], component[0]))
+        .flatten()
+        .collect()
+}
+
+/// For each rule, the lengths of the strings it matches up to `max_length`. Computed for one
+/// length after the other: An alternative matches a length if its symbols can be split into
+/// parts of lengths that they match. As rules don't match the empty string, only alternatives
+/// with a single symbol depend on the same length, so these are repeated until nothing changes.
+fn lengths(rules: &Rules, max_length: usize) -> HashMap<usize, BTreeSet<usize>> {
+    let alternatives: Vec<(usize, Vec<Vec<Symbol>>)> = rules
+        .iter()
+        .map(|(&num, rule)| (num, rule.alternatives()))
+        .collect();
+    let mut lengths: HashMap<usize, BTreeSet<usize>> =
+        rules.keys().map(|&num| (num, BTreeSet::new())).collect();
+
+    for length in 1..=max_length {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (num, alts) in &alternatives {
+                if lengths[num].contains(&length) {
+                    continue;
+                }
+                let matches = alts.iter().any(|alternative| {
+                    // The lengths that a prefix of the alternative can match
+                    let mut prefix: BTreeSet<usize> = BTreeSet::new();
+                    prefix.insert(0);
+                    for symbol in alternative {
+                        prefix = prefix
+                            .iter()
+                            .flat_map(|&before| match symbol {
+                                Symbol::Char(_) => vec![before + 1],
+                                Symbol::Rule(r) => lengths
+                                    .get(r)
+                                    .map_or(vec![], |l| l.iter().map(|&l| before + l).collect()),
+                            })
+                            .filter(|&l| l <= length)
+                            .collect();
+                    }
+                    prefix.contains(&length)
+                });
+                if matches {
+                    lengths.get_mut(num).unwrap().insert(length);
+                    changed = true;
+                }
+            }
+        }
+    }
+    lengths
+}
+
+fn list(numbers: impl IntoIterator<Item = usize>) -> String {
+    let list = numbers.into_iter().join(", ");
+    if list.is_empty() {
+        "none".to_string()
+    } else {
+        list
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Start rule: {}", self.start)?;
+        let undefined = self
+            .undefined
+            .iter()
+            .map(|(num, by)| format!("{} (referenced by {})", num, list(by.iter().copied())))
+            .join(", ");
+        writeln!(
+            f,
+            "Undefined rules: {}",
+            if undefined.is_empty() {
+                "none"
+            } else {
+                &undefined
+            }
+        )?;
+        writeln!(
+            f,
+            "Unreachable rules: {}",
+            list(self.unreachable.iter().copied())
+        )?;
+        writeln!(
+            f,
+            "Recursive rules: {}",
+            list(self.recursive.iter().copied())
+        )?;
+        writeln!(
+            f,
+            "Left recursive rules: {}",
+            list(self.left_recursive.iter().copied())
+        )?;
+        write!(f, "Lengths (up to {}):", self.max_length)?;
+        for (num, lengths) in &self.lengths {
+            write!(f, "\n{:>5}: {}", num, list(lengths.lengths.iter().copied()))?;
+            if lengths.unbounded {
+                write!(f, " ...")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The grammar in EBNF, e.g. `r0 = r4, r1, r5 ;` and `r4 = "a" ;`, sorted by rule number.
+pub fn ebnf(rules: &Rules) -> String {
+    fn expression(rule: &Rule) -> String {
+        match rule {
+            Rule::Char('"') => "'\"'".to_string(),
+            Rule::Char(c) => format!("\"{}\"", c),
+            Rule::List(list) => list.iter().map(|n| format!("r{}", n)).join(", "),
+            Rule::Alt(alts) => alts
+                .iter()
+                .map(|alt| match alt {
+                    Rule::Alt(_) => format!("({})", expression(alt)),
+                    _ => expression(alt),
+                })
+                .join(" | "),
+        }
+    }
+
+    rules
+        .iter()
+        .sorted_by_key(|(&num, _)| num)
+        .map(|(num, rule)| format!("r{} = {} ;\n", num, expression(rule)))
+        .collect()
+}
+
+/// The dependencies between the rules as a Graphviz graph, with the characters that rules
+/// match in their labels. Undefined rules are red, unreachable ones dashed and recursive ones
+/// bold.
+pub fn dot(rules: &Rules, analysis: &Analysis) -> String {
+    let mut dot = String::from("digraph rules {\n");
+    let nodes = rules.keys().chain(analysis.undefined.keys()).sorted();
+    for &num in nodes {
+        let label = match rules.get(&num) {
+            Some(Rule::Char(c)) => format!("{}: {:?}", num, c.to_string()),
+            _ => num.to_string(),
+        };
+        let mut attributes = vec![format!("label={:?}", label)];
+        if analysis.undefined.contains_key(&num) {
+            attributes.push("color=red".to_string());
+        }
+        let mut styles = Vec::new();
+        if analysis.unreachable.contains(&num) {
+            styles.push("dashed");
+        }
+        if analysis.recursive.contains(&num) {
+            styles.push("bold");
+        }
+        if !styles.is_empty() {
+            attributes.push(format!("style={:?}", styles.join(",")));
+        }
+        dot.push_str(&format!("    {} [{}];\n", num, attributes.join(", ")));
+    }
+    for (&num, rule) in rules.iter().sorted_by_key(|(&num, _)| num) {
+        for reference in rule.references().into_iter().unique() {
+            dot.push_str(&format!("    {} -> {};\n", num, reference));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rules: &[(usize, Rule)]) -> Rules {
+        rules.iter().cloned().collect()
+    }
+
+    fn alt(lists: &[&[usize]]) -> Rule {
+        Rule::Alt(lists.iter().map(|l| Rule::List(l.to_vec())).collect())
+    }
+
+    #[test]
+    fn test_matcher() {
+        // Balanced `a` and `b` with left recursion in rule 3
+        let rules = rules(&[
+            (0, alt(&[&[1, 2], &[1, 0, 2]])),
+            (1, Rule::Char('a')),
+            (2, Rule::Char('b')),
+            (3, alt(&[&[3, 1], &[1]])),
+        ]);
+        let matcher = Matcher::new(&rules);
+        assert!(matcher.matches(0, &("a".repeat(30) + &"b".repeat(30))));
+        assert!(!matcher.matches(0, &("a".repeat(30) + &"b".repeat(29))));
+        assert!(matcher.matches(3, "aaaa"));
+        assert!(!matcher.matches(4, "a"));
+    }
+
+    #[test]
+    fn test_analysis() {
+        let rules = rules(&[
+            (0, Rule::List(vec![4, 1])),
+            (1, alt(&[&[4], &[1, 4], &[7]])),
+            (2, alt(&[&[4, 2, 5], &[5]])),
+            (4, Rule::Char('a')),
+            (5, Rule::Char('b')),
+        ]);
+        let analysis = Analysis::new(&rules, 0, 4);
+        assert_eq!(analysis.undefined.keys().collect::<Vec<_>>(), vec![&7]);
+        assert_eq!(analysis.undefined[&7], vec![1].into_iter().collect());
+        assert_eq!(analysis.unreachable, vec![2, 5].into_iter().collect());
+        assert_eq!(analysis.recursive, vec![1, 2].into_iter().collect());
+        assert_eq!(analysis.left_recursive, vec![1].into_iter().collect());
+
+        let lengths = |num| {
+            let l = &analysis.lengths[&num];
+            (l.lengths.iter().copied().collect::<Vec<_>>(), l.unbounded)
+        };
+        assert_eq!(lengths(0), (vec![2, 3, 4], true));
+        assert_eq!(lengths(2), (vec![1, 3], true));
+        assert_eq!(lengths(4), (vec![1], false));
+
+        let report = analysis.to_string();
+        assert!(report.contains("Undefined rules: 7 (referenced by 1)"));
+        assert!(report.contains("Left recursive rules: 1\n"));
+    }
+
+    #[test]
+    fn test_export() {
+        let rules = rules(&[
+            (0, Rule::List(vec![1, 2])),
+            (1, alt(&[&[2, 1], &[2]])),
+            (2, Rule::Char('"')),
+            (3, Rule::Char('a')),
+        ]);
+        assert_eq!(
+            ebnf(&rules),
+            "r0 = r1, r2 ;\nr1 = r2, r1 | r2 ;\nr2 = '\"' ;\nr3 = \"a\" ;\n"
+        );
+
+        let dot = dot(&rules, &Analysis::new(&rules, 0, 2));
+        assert!(dot.starts_with("digraph rules {\n"));
+        assert!(dot.contains("    1 [label=\"1\", style=\"bold\"];\n"));
+        assert!(dot.contains("    2 [label=\"2: \\\"\\\\\\\"\\\"\"];\n"));
+        assert!(dot.contains("    3 [label=\"3: \\\"a\\\"\", style=\"dashed\"];\n"));
+        assert!(dot.contains("    0 -> 1;\n    0 -> 2;\n    1 -> 2;\n    1 -> 1;\n"));
+    }
+}
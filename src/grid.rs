@@ -0,0 +1,324 @@
+//! A rectangular 2D grid, for the days whose input is a map of characters.
+//!
+//! Positions are `(x, y)` with `x` being the column and `y` the row, starting at the top left.
+//! Directions are `(dx, dy)` offsets, e.g. `(1, -1)` is up and to the right.
+
+use crate::parse::{ParseError, Text};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Pos = (usize, usize);
+
+/// Up, right, down and left.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Clockwise starting at the top left.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid from the cells in row order, the number of cells has to be `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "Expected {}x{} cells",
+            width,
+            height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, f: impl FnMut(Pos) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(f)
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    /// Parses a map with one character per cell, e.g. `#` for a tree and `.` for an open square.
+    /// `cell` converts a character, or returns `None` if it isn't valid, in which case the error
+    /// says that `expected` was expected. All rows have to be the same length, and an empty map
+    /// or an empty first row is an error, so the grid is never empty.
+    pub fn parse<'a>(
+        text: &Text<'a>,
+        part: &'a str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in part.lines() {
+            let mut row_width = 0;
+            for (c, char_part) in text.chars(line) {
+                if width == Some(row_width) {
+                    return Err(text.error(char_part, "end of row, as the rows above"));
+                }
+                cells.push(cell(c).ok_or_else(|| text.error(char_part, expected))?);
+                row_width += 1;
+            }
+            match width {
+                Some(w) if w != row_width => {
+                    return Err(text.error_after(line, &format!("a row of {} cells", w)))
+                }
+                None if row_width == 0 => return Err(text.error(line, expected)),
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) => Ok(Grid::new(width, height, cells)),
+            None => Err(text.error(part, "a map")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The cell at the position, or `None` if it's outside the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.index(pos)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at the position as if the grid was repeated infinitely in all directions. Panics
+    /// if the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// The position one step in the direction, or `None` if that's outside the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if self.contains((x, y)) {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// The positions above, right, below and left of the position that are in the grid.
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// Like [`Grid::neighbours_4`] but including diagonals.
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The cells seen from the position when looking in the direction, up to the edge of the grid
+    /// (not including the cell at the position itself).
+    pub fn ray(&self, pos: Pos, direction: (isize, isize)) -> impl Iterator<Item = (Pos, &T)> {
+        let mut current = pos;
+        std::iter::from_fn(move || {
+            current = self.step(current, direction)?;
+            Some((current, &self[current]))
+        })
+    }
+
+    /// All positions in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "Column {} outside of grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The number of cells that match the predicate.
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.cells.iter().filter(|&c| predicate(c)).count()
+    }
+
+    fn index(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "Position {:?} outside of grid", pos);
+        &self.cells[self.index(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "Position {:?} outside of grid", pos);
+        let index = self.index(pos);
+        &mut self.cells[index]
+    }
+}
+
+/// Renders the grid with one line per row, e.g. the same way it was parsed if the cells display
+/// as a single character.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(&Text::new(1, input), input, "`.` or `#`", |c| {
+            if c == '.' || c == '#' {
+                Some(c)
+            } else {
+                None
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let input = "#..\n.#.\n";
+        let grid = parse(input).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], '#');
+        assert_eq!(grid.to_string(), input);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("#..\n.x.").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "`.` or `#`");
+
+        let error = parse("#..\n.#").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a row of 3 cells");
+
+        let error = parse("#..\n.#..").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = parse("").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a map");
+
+        let error = parse("\n#..").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "`.` or `#`");
+    }
+
+    #[test]
+    fn test_access() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid.get((2, 1)), Some(&12));
+        assert_eq!(grid.get((3, 1)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(*grid.get_wrapping(4, -1), 11);
+        assert_eq!(*grid.get_wrapping(-3, 2), 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::from_fn(3, 3, |_| ());
+        assert_eq!(grid.neighbours_4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours_8((2, 0)).count(), 3);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::from_fn(4, 4, |(x, y)| x + 10 * y);
+        let cells: Vec<_> = grid.ray((0, 0), (1, 1)).map(|(_, &c)| c).collect();
+        assert_eq!(cells, vec![11, 22, 33]);
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid.row(1), &[10, 11, 12]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![2, 12]);
+        let sums: Vec<usize> = grid.columns().map(|c| c.sum()).collect();
+        assert_eq!(sums, vec![10, 12, 14]);
+        assert_eq!(grid.map(|c| c % 2 == 0).count(|&even| even), 4);
+    }
+}
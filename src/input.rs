@@ -0,0 +1,140 @@
+//! Loading puzzle input at runtime, from an input directory, a file or stdin.
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Directory that contains `2020/dayNN.txt`, relative to the working directory.
+pub const DEFAULT_DIR: &str = "input";
+
+/// Environment variable to use a different input directory than [`DEFAULT_DIR`].
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to load a day's input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// A directory with the layout `<dir>/2020/dayNN.txt`.
+    Dir(PathBuf),
+    /// A single file, only makes sense when running one day.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// The input directory from `AOC_INPUT_DIR`, or `input` if it's not set.
+    pub fn default_dir() -> Source {
+        let dir = env::var_os(DIR_VAR).map_or_else(|| PathBuf::from(DEFAULT_DIR), PathBuf::from);
+        Source::Dir(dir)
+    }
+
+    /// Whether the source can provide input for more than one day.
+    pub fn is_per_day(&self) -> bool {
+        matches!(self, Source::Dir(_))
+    }
+
+    pub fn load(&self, day: u32) -> Result<String, String> {
+        match self {
+            Source::Dir(dir) => read_file(&path(dir, day), day),
+            Source::File(file) => read_file(file, day),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map_err(|e| {
+                    format!("Could not read input for day {} from stdin: {}", day, e)
+                })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// The path of a day's input in an input directory, e.g. `input/2020/day07.txt`.
+pub fn path(dir: &Path, day: u32) -> PathBuf {
+    dir.join("2020").join(format!("day{:02}.txt", day))
+}
+
+/// The path of the accepted answers in an input directory, e.g. `input/2020/answers.txt`.
+pub fn answers_path(dir: &Path) -> PathBuf {
+    dir.join("2020").join("answers.txt")
+}
+
+fn read_file(file: &Path, day: u32) -> Result<String, String> {
+    fs::read_to_string(file).map_err(|e| {
+        format!(
+            "Could not read input for day {} from {}: {}",
+            day,
+            file.display(),
+            e
+        )
+    })
+}
+
+/// A directory under the system's temp directory for tests with files, removed when dropped.
+#[cfg(test)]
+pub(crate) struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    /// `name` has to be unique among the tests, as they run in parallel.
+    pub fn new(name: &str) -> TempDir {
+        let dir = env::temp_dir().join(format!("aoc-2020-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Writes a file at a path relative to the directory, creating the directories it's in.
+    pub fn write(&self, file: &str, content: &str) {
+        let file = self.0.join(file);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, content).unwrap();
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        assert_eq!(
+            path(Path::new("input"), 7),
+            Path::new("input").join("2020").join("day07.txt")
+        );
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let source = Source::Dir(PathBuf::from("does-not-exist"));
+        let error = source.load(3).unwrap_err();
+        assert!(error.starts_with("Could not read input for day 3 from does-not-exist"));
+    }
+
+    #[test]
+    fn test_load_from_dir() {
+        let dir = TempDir::new("input");
+        dir.write("2020/day07.txt", "light red bags contain no other bags.\n");
+        dir.write("2020/08.txt", "nop +0\n");
+        let source = Source::Dir(dir.path().to_path_buf());
+
+        assert_eq!(
+            source.load(7),
+            Ok("light red bags contain no other bags.\n".to_string())
+        );
+        // Only `dayNN.txt` is an input, not `NN.txt`
+        let error = source.load(8).unwrap_err();
+        assert!(error.starts_with("Could not read input for day 8 from"));
+        assert!(error.contains("day08.txt"));
+    }
+}
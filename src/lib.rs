@@ -1,9 +1,56 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
+//! Solutions for https://adventofcode.com/2020, see `src/bin/aoc.rs` for running them.
 
-    #[test]
-    fn test_something() {
-        assert_eq!(1, 1);
+use crate::parse::ParseError;
+use std::fmt::{self, Display};
+
+pub mod automaton;
+pub mod bench;
+pub mod bitgrid;
+pub mod cli;
+pub mod console;
+pub mod days;
+pub mod grammar;
+pub mod grid;
+pub mod input;
+pub mod modular;
+pub mod multi;
+pub mod parse;
+pub mod runner;
+pub mod verify;
+
+/// The solution for a day's puzzle, implemented by every day in [`days`].
+///
+/// Parsing is separate from solving so that both parts can use the same parsed input, and so
+/// that tools can drive any day without knowing its types.
+pub trait Solution {
+    /// The day of the puzzle, 1 to 25.
+    const DAY: u32;
+
+    /// The parsed puzzle input.
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// The answer of a part, or why the input doesn't have one. Unlike a [`ParseError`], this only
+/// affects the part, e.g. when a program has several ways to be repaired or a result doesn't fit
+/// the answer type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer<T> {
+    Solved(T),
+    /// Why there is no answer.
+    Unsolved(String),
+}
+
+impl<T: Display> Display for Answer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Solved(answer) => answer.fmt(f),
+            Answer::Unsolved(reason) => write!(f, "no answer: {}", reason),
+        }
     }
 }
@@ -0,0 +1,300 @@
+//! Modular arithmetic: solving systems of congruences with the Chinese remainder theorem for the
+//! bus schedules of day 13, and exponentiation and discrete logarithms for the handshake of day
+//! 25.
+
+use num::{BigInt, Integer, Signed, ToPrimitive};
+use std::collections::HashMap;
+use std::fmt;
+
+/// `x ≡ residue (mod modulus)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Congruence<T = u64> {
+    pub residue: T,
+    pub modulus: T,
+}
+
+impl<T> Congruence<T> {
+    pub fn new(residue: T, modulus: T) -> Congruence<T> {
+        Congruence { residue, modulus }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Congruence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x ≡ {} (mod {})", self.residue, self.modulus)
+    }
+}
+
+/// The solution of a system of congruences, as a single congruence with the least common
+/// multiple of the moduli. Only uses a `BigInt` if that doesn't fit in an `i64`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Combined {
+    Small(Congruence<u64>),
+    Big(Congruence<BigInt>),
+}
+
+impl Combined {
+    /// The smallest non-negative solution.
+    pub fn residue(&self) -> BigInt {
+        match self {
+            Combined::Small(c) => c.residue.into(),
+            Combined::Big(c) => c.residue.clone(),
+        }
+    }
+
+    pub fn modulus(&self) -> BigInt {
+        match self {
+            Combined::Small(c) => c.modulus.into(),
+            Combined::Big(c) => c.modulus.clone(),
+        }
+    }
+
+    /// The smallest non-negative solution, if it fits in a `u64`.
+    pub fn residue_u64(&self) -> Option<u64> {
+        match self {
+            Combined::Small(c) => Some(c.residue),
+            Combined::Big(c) => c.residue.to_u64(),
+        }
+    }
+}
+
+impl fmt::Display for Combined {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combined::Small(c) => c.fmt(f),
+            Combined::Big(c) => c.fmt(f),
+        }
+    }
+}
+
+/// A congruence that contradicts the ones before it, so that there's no solution.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NoSolution {
+    /// The index of the congruence.
+    pub index: usize,
+}
+
+impl fmt::Display for NoSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No solution: congruence {} contradicts the ones before it",
+            self.index
+        )
+    }
+}
+
+/// Solves a system of congruences, combining one after the other with the extended Euclidean
+/// algorithm. The moduli don't need to be coprime, e.g. `x ≡ 2 (mod 4)` and `x ≡ 4 (mod 6)` give
+/// `x ≡ 10 (mod 12)`, but then there might not be a solution (e.g. with `x ≡ 3 (mod 6)`).
+///
+/// Panics if a modulus is 0.
+pub fn crt(congruences: &[Congruence]) -> Result<Combined, NoSolution> {
+    let mut combined = Combined::Small(Congruence::new(0, 1));
+    for (index, c) in congruences.iter().enumerate() {
+        assert!(c.modulus > 0, "Modulus must be greater than 0");
+        let next = Congruence::new(c.residue % c.modulus, c.modulus);
+        combined = match combined {
+            Combined::Small(small) => {
+                let lcm = (small.modulus / small.modulus.gcd(&next.modulus)) as u128
+                    * next.modulus as u128;
+                if lcm <= i64::MAX as u128 {
+                    // All intermediate values fit in an i128
+                    let merged = merge::<i128>(&convert(&small), &convert(&next))
+                        .ok_or(NoSolution { index })?;
+                    Combined::Small(Congruence::new(
+                        merged.residue as u64,
+                        merged.modulus as u64,
+                    ))
+                } else {
+                    let merged = merge::<BigInt>(&convert(&small), &convert(&next))
+                        .ok_or(NoSolution { index })?;
+                    Combined::Big(merged)
+                }
+            }
+            Combined::Big(big) => {
+                Combined::Big(merge(&big, &convert(&next)).ok_or(NoSolution { index })?)
+            }
+        };
+    }
+    Ok(combined)
+}
+
+fn convert<T: From<u64>>(c: &Congruence<u64>) -> Congruence<T> {
+    Congruence::new(c.residue.into(), c.modulus.into())
+}
+
+/// Combines two congruences with residues smaller than their moduli into one, `None` if they
+/// contradict each other.
+fn merge<T: Integer + Signed + Clone>(
+    a: &Congruence<T>,
+    b: &Congruence<T>,
+) -> Option<Congruence<T>> {
+    // With a.modulus * x + b.modulus * y = g, the residue
+    // a.residue + a.modulus * x * difference / g solves both, and so does adding multiples of
+    // the lcm, which is a.modulus * step.
+    let gcd = a.modulus.extended_gcd(&b.modulus);
+    let g = gcd.gcd;
+    let difference = b.residue.clone() - a.residue.clone();
+    if !difference.is_multiple_of(&g) {
+        return None;
+    }
+    let step = b.modulus.clone() / g.clone();
+    let k = (difference / g).mod_floor(&step) * gcd.x.mod_floor(&step);
+    let modulus = a.modulus.clone() * step.clone();
+    let residue = a.residue.clone() + a.modulus.clone() * k.mod_floor(&step);
+    Some(Congruence::new(residue, modulus))
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// `base^exponent mod modulus` by square-and-multiply.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    let mut square = base % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, square, modulus);
+        }
+        square = mul_mod(square, square, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The smallest `x` with `base^x ≡ target (mod modulus)`, or `None` if there's none.
+///
+/// Uses baby-step giant-step, which needs about `sqrt(modulus)` time and memory: With `m` the
+/// square root rounded up, the powers `base^j` for `j < m` are checked directly, and every other
+/// `x` smaller than the modulus (which is as far as the powers go before repeating) is
+/// `i * m - j` for `i` and `j` from 1 to `m`. So `base^(i * m) ≡ target * base^j` is looked up in
+/// a table of the right-hand sides. That equation implies a solution if `base` and `modulus` are
+/// coprime; otherwise candidates are checked, and a solution might be missed.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    let target = target % modulus;
+    let m = num::integer::sqrt(modulus) + 1;
+
+    let mut power = 1 % modulus;
+    for j in 0..m {
+        if power == target {
+            return Some(j);
+        }
+        power = mul_mod(power, base, modulus);
+    }
+    // Now power is base^m
+
+    // For each value, the largest j, for the smallest x
+    let mut table = HashMap::new();
+    let mut value = target;
+    for j in 1..=m {
+        value = mul_mod(value, base, modulus);
+        table.insert(value, j);
+    }
+
+    let mut giant = 1 % modulus;
+    for i in 1..=m {
+        giant = mul_mod(giant, power, modulus);
+        if let Some(&j) = table.get(&giant) {
+            let x = i * m - j;
+            if mod_pow(base, x, modulus) == target {
+                return Some(x);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(congruences: &[(u64, u64)]) -> Result<Combined, NoSolution> {
+        let congruences: Vec<_> = congruences
+            .iter()
+            .map(|&(residue, modulus)| Congruence::new(residue, modulus))
+            .collect();
+        crt(&congruences)
+    }
+
+    #[test]
+    fn test_coprime() {
+        assert_eq!(
+            solve(&[(2, 3), (3, 5), (2, 7)]),
+            Ok(Combined::Small(Congruence::new(23, 105)))
+        );
+        assert_eq!(solve(&[]), Ok(Combined::Small(Congruence::new(0, 1))));
+        assert_eq!(
+            solve(&[(10, 7)]),
+            Ok(Combined::Small(Congruence::new(3, 7)))
+        );
+    }
+
+    #[test]
+    fn test_not_coprime() {
+        assert_eq!(
+            solve(&[(2, 4), (4, 6)]),
+            Ok(Combined::Small(Congruence::new(10, 12)))
+        );
+        assert_eq!(
+            solve(&[(2, 4), (4, 6), (3, 6)]),
+            Err(NoSolution { index: 2 })
+        );
+        assert_eq!(
+            solve(&[(1, 4), (2, 6)]).unwrap_err().to_string(),
+            "No solution: congruence 1 contradicts the ones before it"
+        );
+    }
+
+    #[test]
+    fn test_big() {
+        // Primes close to 2^32 and 2^63, so the moduli overflow a u64
+        let primes = [4294967291, 4294967279, 4294967231, 9223372036854775783];
+        let congruences: Vec<_> = primes
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (p - 1 - i as u64, p))
+            .collect();
+        let combined = solve(&congruences).unwrap();
+        assert!(matches!(combined, Combined::Big(_)));
+        assert_eq!(combined.residue_u64(), None);
+        let product = primes.iter().map(|&p| BigInt::from(p)).product::<BigInt>();
+        assert_eq!(combined.modulus(), product);
+        for &(residue, modulus) in &congruences {
+            assert_eq!(combined.residue() % modulus, BigInt::from(residue));
+        }
+
+        // Just above what's combined without a BigInt, but the solution still fits in a u64
+        let combined = solve(&[(1, 1 << 32), (2, (1 << 31) + 1)]).unwrap();
+        assert!(matches!(combined, Combined::Big(_)));
+        let residue = combined.residue_u64().unwrap();
+        assert_eq!((residue % (1 << 32), residue % ((1 << 31) + 1)), (1, 2));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(7, 8, 20201227), 5764801);
+        assert_eq!(mod_pow(17807724, 8, 20201227), 14897079);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(3, 5, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, u64::MAX, u64::MAX), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(7, 1, 20201227), Some(0));
+        let x = 12345678;
+        assert_eq!(discrete_log(7, mod_pow(7, x, 20201227), 20201227), Some(x));
+
+        // 2 generates only 1, 2 and 4 modulo 7
+        assert_eq!(discrete_log(2, 4, 7), Some(2));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        // Not coprime
+        assert_eq!(discrete_log(2, 8, 24), Some(3));
+        assert_eq!(discrete_log(2, 16, 24), Some(4));
+        assert_eq!(discrete_log(2, 3, 24), None);
+    }
+}
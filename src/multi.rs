@@ -0,0 +1,239 @@
+//! Running the days against the inputs of multiple users, to check that the solutions don't
+//! depend on properties of one particular input.
+//!
+//! The inputs are in a directory with one input directory per user, e.g.
+//! `inputs/alice/2020/day07.txt` and `inputs/bob/2020/day07.txt`.
+
+use crate::days;
+use crate::input::{self, Source};
+use std::any::Any;
+use std::fs;
+use std::panic;
+use std::path::Path;
+
+/// The result of running a day for a user.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Answers(String, String),
+    /// The user has no input for the day.
+    Missing,
+    /// The input couldn't be parsed or the solution panicked.
+    Failed(String),
+}
+
+impl Outcome {
+    /// The answer of the part as it's shown in the report.
+    fn cell(&self, part: u32) -> &str {
+        match self {
+            Outcome::Answers(one, _) if part == 1 => one,
+            Outcome::Answers(_, two) => two,
+            Outcome::Missing => "-",
+            Outcome::Failed(_) => "FAILED",
+        }
+    }
+}
+
+/// The outcomes for one user, in the order of the selected days.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserResults {
+    pub user: String,
+    pub outcomes: Vec<(u32, Outcome)>,
+}
+
+/// The names of the users in the directory (subdirectories with a `2020` directory), sorted.
+pub fn users(dir: &Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Could not read inputs from {}: {}", dir.display(), e))?;
+    let mut users = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Could not read inputs: {}", e))?;
+        if entry.path().join("2020").is_dir() {
+            users.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    if users.is_empty() {
+        return Err(format!(
+            "No inputs in {}, expected e.g. {}",
+            dir.display(),
+            input::path(&dir.join("<user>"), 1).display()
+        ));
+    }
+    users.sort();
+    Ok(users)
+}
+
+/// Runs a day and returns its answers, or why it failed. Panics are caught too, as solutions
+/// often `unwrap` or `assert` things that are true for one input but not another.
+pub fn run_day(day: u32, input: &str) -> Outcome {
+    let d = days::get(day).expect("valid day");
+    match panic::catch_unwind(|| (d.run)(input)) {
+        Ok(Ok(output)) => Outcome::Answers(output.one, output.two),
+        Ok(Err(e)) => Outcome::Failed(e.to_string()),
+        Err(payload) => Outcome::Failed(format!("Panicked: {}", panic_message(&*payload))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown reason"
+    }
+}
+
+/// Runs the selected days for every user in the directory.
+pub fn run(selection: &[u32], dir: &Path) -> Result<Vec<UserResults>, String> {
+    let users = users(dir)?;
+
+    // The panics are reported with the results, don't print them in between
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let results = users
+        .into_iter()
+        .map(|user| {
+            let source = Source::Dir(dir.join(&user));
+            let outcomes = selection
+                .iter()
+                .map(|&day| {
+                    let outcome = if input::path(&dir.join(&user), day).exists() {
+                        match source.load(day) {
+                            Ok(input) => run_day(day, &input),
+                            Err(e) => Outcome::Failed(e),
+                        }
+                    } else {
+                        Outcome::Missing
+                    };
+                    (day, outcome)
+                })
+                .collect();
+            UserResults { user, outcomes }
+        })
+        .collect();
+    panic::set_hook(hook);
+
+    Ok(results)
+}
+
+/// Prints the report, see [`format_report`].
+pub fn print_report(results: &[UserResults]) {
+    print!("{}", format_report(results));
+}
+
+/// Formats a table with a row per day and part and a column per user, followed by the failures.
+pub fn format_report(results: &[UserResults]) -> String {
+    let widths: Vec<usize> = results
+        .iter()
+        .map(|r| {
+            r.outcomes
+                .iter()
+                .flat_map(|(_, o)| vec![o.cell(1).len(), o.cell(2).len()])
+                .chain(Some(r.user.len()))
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut report = String::new();
+    let mut push_row = |first: String, cells: Vec<&str>| {
+        let mut line = first;
+        for (cell, width) in cells.iter().zip(&widths) {
+            line.push_str(&format!("  {:width$}", cell, width = width));
+        }
+        report.push_str(line.trim_end());
+        report.push('\n');
+    };
+
+    push_row(
+        "Day  Part".to_string(),
+        results.iter().map(|r| r.user.as_str()).collect(),
+    );
+    let days = results.first().map_or(0, |r| r.outcomes.len());
+    for i in 0..days {
+        let day = results[0].outcomes[i].0;
+        for part in 1..=2 {
+            push_row(
+                format!("{:>3}  {:>4}", day, part),
+                results.iter().map(|r| r.outcomes[i].1.cell(part)).collect(),
+            );
+        }
+    }
+
+    let failures: Vec<_> = results
+        .iter()
+        .flat_map(|r| {
+            r.outcomes.iter().filter_map(move |(day, o)| match o {
+                Outcome::Failed(message) => Some((&r.user, day, message)),
+                _ => None,
+            })
+        })
+        .collect();
+    if !failures.is_empty() {
+        report.push_str("\nFailures:\n");
+        for (user, day, message) in failures {
+            report.push_str(&format!("{}, day {}: {}\n", user, day, message));
+        }
+    }
+    report
+}
+
+/// Runs the selected days for every user and prints the report.
+pub fn print_multi(selection: &[u32], dir: &Path) -> Result<(), String> {
+    print_report(&run(selection, dir)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::TempDir;
+
+    #[test]
+    fn test_run_day() {
+        assert_eq!(
+            run_day(1, "1721\n979\n366\n299\n675\n1456\n"),
+            Outcome::Answers("514579".to_string(), "241861950".to_string())
+        );
+        match run_day(1, "1721\nx\n") {
+            Outcome::Failed(message) => assert!(message.starts_with("Day 1, line 2, column 1")),
+            outcome => panic!("Unexpected {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn test_missing_dir() {
+        assert!(users(Path::new("does-not-exist")).is_err());
+    }
+
+    #[test]
+    fn test_run_dir() {
+        let dir = TempDir::new("multi");
+        dir.write("alice/2020/day01.txt", "1721\n979\n366\n299\n675\n1456\n");
+        // Not named `dayNN.txt`, so the input counts as missing
+        dir.write("bob/2020/01.txt", "1721\n979\n366\n299\n675\n1456\n");
+        dir.write("carol/2020/day01.txt", "1721\nx\n");
+        // Not a user, as there's no `2020` directory
+        dir.write("notes/day01.txt", "");
+
+        let results = run(&[1, 2], dir.path()).unwrap();
+        let users: Vec<_> = results.iter().map(|r| r.user.as_str()).collect();
+        assert_eq!(users, vec!["alice", "bob", "carol"]);
+        assert_eq!(
+            results[1].outcomes,
+            vec![(1, Outcome::Missing), (2, Outcome::Missing)]
+        );
+        assert_eq!(
+            format_report(&results),
+            "Day  Part  alice      bob  carol
+  1     1  514579     -    FAILED
+  1     2  241861950  -    FAILED
+  2     1  -          -    -
+  2     2  -          -    -
+
+Failures:
+carol, day 1: Day 1, line 2, column 1: expected a number, found \"x\"
+"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Parse errors that point to a line and column of the puzzle input.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column in characters, starting at 1.
+    pub column: usize,
+    /// What was expected at the position, e.g. "a number".
+    pub expected: String,
+    /// What was found instead, e.g. the part of the line that isn't a number.
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// The puzzle input of a day, for creating errors from the parts of it that fail to parse.
+///
+/// All the `part` arguments have to be slices of the input (e.g. from `lines` or `split`), so
+/// that the line and column can be calculated from where they start.
+#[derive(Clone, Copy, Debug)]
+pub struct Text<'a> {
+    day: u32,
+    input: &'a str,
+}
+
+impl<'a> Text<'a> {
+    pub fn new(day: u32, input: &'a str) -> Text<'a> {
+        Text { day, input }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.input.lines()
+    }
+
+    /// An error at the start of `part`.
+    pub fn error(&self, part: &str, expected: &str) -> ParseError {
+        let offset = self.offset(part);
+        let before = &self.input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let found = if !part.is_empty() {
+            format!("{:?}", part)
+        } else if offset == self.input.len() {
+            "end of input".to_string()
+        } else if self.input[offset..].starts_with('\n') {
+            "end of line".to_string()
+        } else {
+            let rest = self.input[offset..].lines().next().unwrap_or("");
+            format!("{:?}", rest)
+        };
+        ParseError {
+            day: self.day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.to_string(),
+            found,
+        }
+    }
+
+    /// An error right after the end of `part`, for something that's missing.
+    pub fn error_after(&self, part: &str, expected: &str) -> ParseError {
+        let end = self.offset(part) + part.len();
+        self.error(&self.input[end..end], expected)
+    }
+
+    /// An error at the end of the input, e.g. for missing lines.
+    pub fn error_at_end(&self, expected: &str) -> ParseError {
+        self.error(&self.input[self.input.len()..], expected)
+    }
+
+    /// Parses all of `part` with `FromStr`.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        separator: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(separator)
+            .ok_or_else(|| self.error_after(part, &format!("{:?}", separator)))
+    }
+
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error(part, &format!("{:?}", prefix)))
+    }
+
+    pub fn strip_suffix(&self, part: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+        part.strip_suffix(suffix)
+            .ok_or_else(|| self.error_after(part, &format!("{:?}", suffix)))
+    }
+
+    /// The characters of `part` as slices, so that errors can point to a single character.
+    pub fn chars(&self, part: &'a str) -> impl Iterator<Item = (char, &'a str)> {
+        part.char_indices()
+            .map(move |(i, c)| (c, &part[i..i + c.len_utf8()]))
+    }
+
+    fn offset(&self, part: &str) -> usize {
+        let start = self.input.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        assert!(
+            offset <= self.input.len(),
+            "Part {:?} is not a slice of the input",
+            part
+        );
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_position() {
+        let input = "1-3 a: abc\n2-x b: cde\n";
+        let text = Text::new(2, input);
+        let line = text.lines().nth(1).unwrap();
+        let (range, _) = text.split_once(line, " ").unwrap();
+        let (_, to) = text.split_once(range, "-").unwrap();
+
+        let error = text.parse::<usize>(to, "a number").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                day: 2,
+                line: 2,
+                column: 3,
+                expected: "a number".to_string(),
+                found: "\"x\"".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Day 2, line 2, column 3: expected a number, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_error_missing() {
+        let input = "abc\ndef";
+        let text = Text::new(1, input);
+        let line = text.lines().next().unwrap();
+
+        let error = text.split_once(line, ": ").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.expected, "\": \"");
+        assert_eq!(error.found, "end of line");
+
+        let error = text.error_at_end("another line");
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.found, "end of input");
+    }
+
+    #[test]
+    fn test_error_column_counts_chars() {
+        let input = "äöü x";
+        let text = Text::new(1, input);
+        let (c, part) = text.chars(input).nth(4).unwrap();
+        assert_eq!(c, 'x');
+        assert_eq!(text.error(part, "a letter").column, 5);
+    }
+}
@@ -0,0 +1,322 @@
+//! Running days and printing their answers, used by the `aoc` and `dayNN` binaries.
+
+use crate::bench::Timing;
+use crate::days;
+use crate::input::Source;
+use std::fmt::Write;
+
+/// The answers of a single day.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Answers {
+    pub day: u32,
+    pub one: String,
+    pub two: String,
+    pub timing: Timing,
+}
+
+/// How to print answers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// A table for humans.
+    Table,
+    /// A JSON array with an object per part, see [`format_json`].
+    Json,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Result<Format, String> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format {:?}, use `table` or `json`", s)),
+        }
+    }
+}
+
+/// Parses a selection of days like `7`, `1..=25`, `1..5` or `all`. Multiple specs can be given.
+pub fn parse_selection<S: AsRef<str>>(specs: &[S]) -> Result<Vec<u32>, String> {
+    if specs.is_empty() {
+        return Err("No days selected, use e.g. `7`, `1..=25` or `all`".to_string());
+    }
+
+    let mut selection = Vec::new();
+    for spec in specs {
+        let spec = spec.as_ref();
+        let last = days::DAYS.len() as u32;
+        let range = if spec == "all" {
+            1..last + 1
+        } else if let Some((from, to)) = spec.split_once("..=") {
+            parse_day(from)?..parse_day(to)? + 1
+        } else if let Some((from, to)) = spec.split_once("..") {
+            // The end is exclusive, so it can be one past the last day
+            let from = parse_day(from)?;
+            let to = match to.parse::<u32>() {
+                Ok(to) if to <= last + 1 => to,
+                _ => return Err(format!("Not a valid end of a range of days: {:?}", to)),
+            };
+            from..to
+        } else {
+            let day = parse_day(spec)?;
+            day..day + 1
+        };
+        if range.is_empty() {
+            return Err(format!("Empty range of days: {}", spec));
+        }
+        selection.extend(range);
+    }
+    Ok(selection)
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    match s.parse::<u32>() {
+        Ok(day) if days::get(day).is_some() => Ok(day),
+        _ => Err(format!("Not a valid day: {:?}", s)),
+    }
+}
+
+/// A day that couldn't be run, e.g. because its input is missing or invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Failure {
+    pub day: u32,
+    pub message: String,
+}
+
+/// Runs the selected days against their inputs from the source, failing at the first day that
+/// can't be run.
+pub fn run(selection: &[u32], source: &Source) -> Result<Vec<Answers>, String> {
+    run_each(selection, source)?
+        .into_iter()
+        .map(|result| result.map_err(|failure| failure.message))
+        .collect()
+}
+
+/// Runs each selected day against its input from the source, a day that can't be run doesn't
+/// stop the others.
+pub fn run_each(
+    selection: &[u32],
+    source: &Source,
+) -> Result<Vec<Result<Answers, Failure>>, String> {
+    if selection.len() > 1 && !source.is_per_day() {
+        return Err("A single input can only be used when running a single day".to_string());
+    }
+
+    let results = selection
+        .iter()
+        .map(|&day| {
+            let input = source.load(day);
+            let d = days::get(day).expect("valid day");
+            let output = input.and_then(|input| (d.run)(&input).map_err(|e| e.to_string()));
+            match output {
+                Ok(output) => Ok(Answers {
+                    day,
+                    one: output.one,
+                    two: output.two,
+                    timing: output.timing,
+                }),
+                Err(message) => Err(Failure { day, message }),
+            }
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Prints the answers as a table with one row per day.
+pub fn print_table(answers: &[Answers]) {
+    let width = answers
+        .iter()
+        .map(|a| a.one.len())
+        .chain(Some("Part 1".len()))
+        .max()
+        .unwrap();
+    println!("Day  {:width$}  Part 2", "Part 1", width = width);
+    for a in answers {
+        println!("{:>3}  {:width$}  {}", a.day, a.one, a.two, width = width);
+    }
+}
+
+/// Formats the answers as a JSON array with an object per part, like this:
+///
+/// ```json
+/// [
+///   {"day": 1, "part": 1, "answer": "1006176", "parse_ns": 7402, "solve_ns": 15222},
+///   {"day": 1, "part": 2, "answer": "199132160", "parse_ns": 7402, "solve_ns": 448063}
+/// ]
+/// ```
+///
+/// Answers are always strings, as some of them aren't numbers. `parse_ns` is how long parsing the
+/// input took in nanoseconds (the same for both parts), `solve_ns` how long solving the part took.
+///
+/// A day that couldn't be run has a single object with the reason instead, like
+/// `{"day": 2, "error": "Day 2, line 1, column 12: expected \": \", found end of line"}`.
+pub fn format_json(results: &[Result<Answers, Failure>]) -> String {
+    let objects: Vec<String> = results
+        .iter()
+        .flat_map(|result| match result {
+            Ok(answers) => json_parts(answers),
+            Err(failure) => vec![format!(
+                "{{\"day\": {}, \"error\": {}}}",
+                failure.day,
+                json_string(&failure.message)
+            )],
+        })
+        .collect();
+    if objects.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n  {}\n]", objects.join(",\n  "))
+    }
+}
+
+fn json_parts(a: &Answers) -> Vec<String> {
+    vec![(1, &a.one, a.timing.part1), (2, &a.two, a.timing.part2)]
+        .into_iter()
+        .map(|(part, answer, solve)| {
+            format!(
+                "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+                a.day,
+                part,
+                json_string(answer),
+                a.timing.parse.as_nanos(),
+                solve.as_nanos()
+            )
+        })
+        .collect()
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Runs the selected days and prints the answers in the format. Fails after printing the
+/// answers of the other days if any day couldn't be run.
+pub fn print_days(selection: &[u32], source: &Source, format: Format) -> Result<(), String> {
+    let results = run_each(selection, source)?;
+    if format == Format::Json {
+        println!("{}", format_json(&results));
+    }
+    let mut answers = Vec::new();
+    let mut failures = Vec::new();
+    for result in results {
+        match result {
+            Ok(a) => answers.push(a),
+            Err(failure) => failures.push(failure.message),
+        }
+    }
+    if format == Format::Table && !answers.is_empty() {
+        print_table(&answers);
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::TempDir;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection(&["7"]), Ok(vec![7]));
+        assert_eq!(parse_selection(&["1..=3"]), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_selection(&["1..3"]), Ok(vec![1, 2]));
+        assert_eq!(parse_selection(&["3", "20..=21"]), Ok(vec![3, 20, 21]));
+        assert_eq!(parse_selection(&["all"]).unwrap().len(), 25);
+
+        assert!(parse_selection::<&str>(&[]).is_err());
+        assert!(parse_selection(&["0"]).is_err());
+        assert!(parse_selection(&["26"]).is_err());
+        assert!(parse_selection(&["1..=26"]).is_err());
+        assert!(parse_selection(&["5..=1"]).is_err());
+        assert!(parse_selection(&["x"]).is_err());
+
+        assert_eq!(parse_selection(&["1..26"]).unwrap().len(), 25);
+        assert_eq!(parse_selection(&["24..26"]), Ok(vec![24, 25]));
+        assert!(parse_selection(&["1..27"]).is_err());
+        assert_eq!(
+            parse_selection(&["3..3"]),
+            Err("Empty range of days: 3..3".to_string())
+        );
+        assert!(parse_selection(&["5..2"]).is_err());
+    }
+
+    #[test]
+    fn test_format_json() {
+        let answers = vec![Ok(Answers {
+            day: 21,
+            one: "5".to_string(),
+            two: "a,\"b\"".to_string(),
+            timing: Timing {
+                parse: Duration::from_nanos(10),
+                part1: Duration::from_nanos(20),
+                part2: Duration::from_nanos(30),
+            },
+        })];
+        assert_eq!(
+            format_json(&answers),
+            r#"[
+  {"day": 21, "part": 1, "answer": "5", "parse_ns": 10, "solve_ns": 20},
+  {"day": 21, "part": 2, "answer": "a,\"b\"", "parse_ns": 10, "solve_ns": 30}
+]"#
+        );
+        assert_eq!(format_json(&[]), "[]");
+
+        let failure = Failure {
+            day: 2,
+            message: "Day 2, line 1, column 12: expected \": \"".to_string(),
+        };
+        assert_eq!(
+            format_json(&[Err(failure)]),
+            r#"[
+  {"day": 2, "error": "Day 2, line 1, column 12: expected \": \""}
+]"#
+        );
+    }
+
+    #[test]
+    fn test_run_dir() {
+        let dir = TempDir::new("runner");
+        dir.write("2020/day01.txt", "1721\n979\n366\n299\n675\n1456\n");
+        dir.write("2020/02.txt", "1-3 a: abcde\n");
+        let source = Source::Dir(dir.path().to_path_buf());
+
+        let answers = run(&[1], &source).unwrap();
+        assert_eq!(answers.len(), 1);
+        assert_eq!(
+            (answers[0].one.as_str(), answers[0].two.as_str()),
+            ("514579", "241861950")
+        );
+
+        let error = run(&[1, 2], &source).unwrap_err();
+        assert!(error.starts_with("Could not read input for day 2 from"));
+
+        dir.write("2020/day02.txt", "1-3 a abcde\n");
+        let error = run(&[2], &source).unwrap_err();
+        assert!(error.starts_with("Day 2, line 1, column 12"));
+
+        // The other days still run
+        let results = run_each(&[2, 1, 3], &source).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap_err().day, 2);
+        assert_eq!(results[1].as_ref().unwrap().one, "514579");
+        assert!(results[2]
+            .as_ref()
+            .unwrap_err()
+            .message
+            .contains("day03.txt"));
+    }
+}
@@ -0,0 +1,264 @@
+//! Checking answers against the accepted answers recorded in `<input dir>/2020/answers.txt`.
+
+use crate::input::{self, Source};
+use crate::runner::{self, Answers};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Accepted answers by day and part, e.g. `(7, 1)` for part 1 of day 7.
+pub type Accepted = BTreeMap<(u32, u32), String>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no recorded answer for the part yet.
+    Missing,
+    /// The day couldn't be run, e.g. because its input is missing or invalid.
+    Error {
+        message: String,
+    },
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => f.pad("pass"),
+            Status::Fail { .. } => f.pad("FAIL"),
+            Status::Missing => f.pad("missing"),
+            Status::Error { .. } => f.pad("ERROR"),
+        }
+    }
+}
+
+/// The result of verifying a single part.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Verification {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub status: Status,
+}
+
+/// Parses lines like `7 1 213`: the day, the part and the answer (which can contain spaces).
+pub fn parse_answers(s: &str) -> Result<Accepted, String> {
+    let mut accepted = Accepted::new();
+    for (i, line) in s.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let invalid = || format!("Invalid answer on line {}: {:?}", i + 1, line);
+        let mut parts = line.splitn(3, ' ');
+        let day = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(invalid)?;
+        let part = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(invalid)?;
+        let answer = parts.next().ok_or_else(invalid)?;
+        if part != 1 && part != 2 {
+            return Err(invalid());
+        }
+        accepted.insert((day, part), answer.to_string());
+    }
+    Ok(accepted)
+}
+
+pub fn format_answers(accepted: &Accepted) -> String {
+    accepted
+        .iter()
+        .map(|((day, part), answer)| format!("{} {} {}\n", day, part, answer))
+        .collect()
+}
+
+/// Loads the accepted answers, or no answers if the file doesn't exist yet.
+pub fn load_answers(file: &Path) -> Result<Accepted, String> {
+    if !file.exists() {
+        return Ok(Accepted::new());
+    }
+    let content = fs::read_to_string(file)
+        .map_err(|e| format!("Could not read answers from {}: {}", file.display(), e))?;
+    parse_answers(&content).map_err(|e| format!("{} in {}", e, file.display()))
+}
+
+pub fn save_answers(file: &Path, accepted: &Accepted) -> Result<(), String> {
+    fs::write(file, format_answers(accepted))
+        .map_err(|e| format!("Could not write answers to {}: {}", file.display(), e))
+}
+
+/// Compares the answers of each part to the accepted ones.
+pub fn verify(answers: &[Answers], accepted: &Accepted) -> Vec<Verification> {
+    answers
+        .iter()
+        .flat_map(|a| vec![(a.day, 1, &a.one), (a.day, 2, &a.two)])
+        .map(|(day, part, answer)| {
+            let status = match accepted.get(&(day, part)) {
+                Some(expected) if expected == answer => Status::Pass,
+                Some(expected) => Status::Fail {
+                    expected: expected.clone(),
+                },
+                None => Status::Missing,
+            };
+            Verification {
+                day,
+                part,
+                answer: answer.clone(),
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Runs each selected day with its input from the source and compares the answers. A day that
+/// can't be run doesn't stop the others, both of its parts get [`Status::Error`] instead.
+pub fn verify_days(selection: &[u32], source: &Source, accepted: &Accepted) -> Vec<Verification> {
+    selection
+        .iter()
+        .flat_map(|&day| match runner::run(&[day], source) {
+            Ok(answers) => verify(&answers, accepted),
+            Err(message) => (1..=2)
+                .map(|part| Verification {
+                    day,
+                    part,
+                    answer: String::new(),
+                    status: Status::Error {
+                        message: message.clone(),
+                    },
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Adds the answers of the parts that are missing. Failed parts are left alone, an accepted
+/// answer should only be changed by editing the file.
+pub fn record_missing(verifications: &[Verification], accepted: &mut Accepted) -> usize {
+    let mut recorded = 0;
+    for v in verifications {
+        if v.status == Status::Missing {
+            accepted.insert((v.day, v.part), v.answer.clone());
+            recorded += 1;
+        }
+    }
+    recorded
+}
+
+pub fn print_verifications(verifications: &[Verification]) {
+    println!("Day  Part  Status   Answer");
+    for v in verifications {
+        print!("{:>3}  {:>4}  {:7}  {}", v.day, v.part, v.status, v.answer);
+        match &v.status {
+            Status::Fail { expected } => print!(" (expected {})", expected),
+            Status::Error { message } => print!(" ({})", message),
+            _ => {}
+        }
+        println!();
+    }
+}
+
+/// Runs the selected days with inputs from the directory, prints whether the answers match the
+/// accepted ones and optionally records the missing ones. Fails after printing all parts if any
+/// answer doesn't match or any day couldn't be run.
+pub fn print_verify(selection: &[u32], dir: &Path, record: bool) -> Result<(), String> {
+    let file = input::answers_path(dir);
+    let mut accepted = load_answers(&file)?;
+    let verifications = verify_days(selection, &Source::Dir(dir.to_path_buf()), &accepted);
+    print_verifications(&verifications);
+
+    let count = |f: fn(&Status) -> bool| verifications.iter().filter(|v| f(&v.status)).count();
+    let failed = count(|s| matches!(s, Status::Fail { .. }));
+    let errors = count(|s| matches!(s, Status::Error { .. }));
+    let missing = count(|s| *s == Status::Missing);
+    println!(
+        "\n{} passed, {} failed, {} missing, {} errors",
+        count(|s| *s == Status::Pass),
+        failed,
+        missing,
+        errors
+    );
+
+    if record && missing > 0 {
+        let recorded = record_missing(&verifications, &mut accepted);
+        save_answers(&file, &accepted)?;
+        println!("Recorded {} answers in {}", recorded, file.display());
+    }
+
+    match (failed, errors) {
+        (0, 0) => Ok(()),
+        (failed, 0) => Err(format!("{} answers don't match the accepted ones", failed)),
+        (0, errors) => Err(format!("{} parts couldn't be run", errors)),
+        (failed, errors) => Err(format!(
+            "{} answers don't match the accepted ones and {} parts couldn't be run",
+            failed, errors
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::Timing;
+
+    fn answers(day: u32, one: &str, two: &str) -> Answers {
+        Answers {
+            day,
+            one: one.to_string(),
+            two: two.to_string(),
+            timing: Timing::default(),
+        }
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let accepted = parse_answers("1 1 123\n1 2 456\n\n21 2 a,b c\n").unwrap();
+        assert_eq!(accepted[&(1, 2)], "456");
+        assert_eq!(accepted[&(21, 2)], "a,b c");
+        assert_eq!(format_answers(&accepted), "1 1 123\n1 2 456\n21 2 a,b c\n");
+
+        assert!(parse_answers("1 3 123").is_err());
+        assert!(parse_answers("1 1").is_err());
+        assert!(parse_answers("x 1 2").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut accepted = parse_answers("1 1 10\n1 2 20\n2 1 30\n").unwrap();
+        let verifications = verify(&[answers(1, "10", "21"), answers(2, "30", "40")], &accepted);
+        let statuses: Vec<_> = verifications.iter().map(|v| v.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Status::Pass,
+                Status::Fail {
+                    expected: "20".to_string()
+                },
+                Status::Pass,
+                Status::Missing
+            ]
+        );
+
+        assert_eq!(record_missing(&verifications, &mut accepted), 1);
+        assert_eq!(accepted[&(1, 2)], "20");
+        assert_eq!(accepted[&(2, 2)], "40");
+    }
+
+    #[test]
+    fn test_verify_days_with_errors() {
+        let source = Source::Dir(Path::new("does-not-exist").to_path_buf());
+        let verifications = verify_days(&[1, 2], &source, &Accepted::new());
+        let days: Vec<_> = verifications.iter().map(|v| (v.day, v.part)).collect();
+        assert_eq!(days, vec![(1, 1), (1, 2), (2, 1), (2, 2)]);
+        for v in &verifications {
+            match &v.status {
+                Status::Error { message } => assert!(message.contains("Could not read input")),
+                status => panic!("Expected an error, got {:?}", status),
+            }
+        }
+    }
+}